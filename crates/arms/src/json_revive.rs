@@ -1,97 +1,470 @@
 use json::JsonValue;
-use std::mem::transmute;
+use std::error;
+use std::fmt;
+use std::io;
 
 use scx;
 
-pub trait JsonRevive {
-    fn from_json(value: &JsonValue) -> Self;
+/// Reviving a value failed. Contains the path to the offending value, eg. `units[12].angle`.
+#[derive(Debug)]
+pub enum ReviveError {
+    /// A required value is missing or `nil`.
+    Missing(String),
+    /// A value has the wrong type, or is out of range for its field.
+    InvalidType(String, &'static str),
+    /// An ID does not match any known constant.
+    UnknownId(String, scx::UnknownIdError),
+}
+
+impl fmt::Display for ReviveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReviveError::Missing(ref path) => write!(f, "missing value at {}", path),
+            ReviveError::InvalidType(ref path, expected) => write!(f, "expected {} at {}", expected, path),
+            ReviveError::UnknownId(ref path, ref err) => write!(f, "{} at {}", err, path),
+        }
+    }
+}
+
+impl error::Error for ReviveError {
+    fn description(&self) -> &str {
+        match *self {
+            ReviveError::Missing(_) => "missing value",
+            ReviveError::InvalidType(_, _) => "invalid value",
+            ReviveError::UnknownId(_, _) => "unknown ID",
+        }
+    }
+}
+
+impl From<ReviveError> for io::Error {
+    fn from(err: ReviveError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+pub trait JsonRevive: Sized {
+    fn from_json(value: &JsonValue, path: &str) -> Result<Self, ReviveError>;
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        String::from(name)
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+// Read a field that must be present.
+fn required<T, F>(json: &JsonValue, path: &str, name: &str, expected: &'static str, convert: F) -> Result<T, ReviveError>
+    where F: Fn(&JsonValue) -> Option<T>
+{
+    let value = &json[name];
+    if value.is_null() {
+        return Err(ReviveError::Missing(field_path(path, name)));
+    }
+    convert(value).ok_or_else(|| ReviveError::InvalidType(field_path(path, name), expected))
+}
+
+// Read a field that Lua scripts may leave `nil`.
+fn optional<T, F>(json: &JsonValue, path: &str, name: &str, expected: &'static str, default: T, convert: F) -> Result<T, ReviveError>
+    where F: Fn(&JsonValue) -> Option<T>
+{
+    if json[name].is_null() {
+        Ok(default)
+    } else {
+        required(json, path, name, expected, convert)
+    }
+}
+
+fn as_string(value: &JsonValue) -> Option<String> {
+    value.as_str().map(String::from)
+}
+
+/// Revive every member of a JSON array.
+pub fn revive_all<T: JsonRevive>(json: &JsonValue, path: &str) -> Result<Vec<T>, ReviveError> {
+    json.members().enumerate()
+        .map(|(i, member)| T::from_json(member, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+impl JsonRevive for i32 {
+    fn from_json(json: &JsonValue, path: &str) -> Result<i32, ReviveError> {
+        json.as_i32().ok_or_else(|| ReviveError::InvalidType(String::from(path), "an integer"))
+    }
+}
+
+impl JsonRevive for scx::TechnologyId {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::TechnologyId, ReviveError> {
+        json.as_u16()
+            .map(scx::TechnologyId::from_u16)
+            .ok_or_else(|| ReviveError::InvalidType(String::from(path), "a technology"))
+    }
+}
+
+impl JsonRevive for scx::UnitTypeId {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::UnitTypeId, ReviveError> {
+        json.as_u16()
+            .map(scx::UnitTypeId::from_u16)
+            .ok_or_else(|| ReviveError::InvalidType(String::from(path), "a unit type"))
+    }
+}
+
+// Trigger locations and areas are -1 when they are not set. Lua leaves them `nil`.
+const NO_TILE: u32 = !0;
+
+fn tile(json: &JsonValue, path: &str, name: &str) -> Result<u32, ReviveError> {
+    optional(json, path, name, "a tile coordinate", -1, JsonValue::as_i32)
+        .map(|tile| if tile < 0 { NO_TILE } else { tile as u32 })
 }
 
 impl JsonRevive for scx::Coordinate {
-    fn from_json(json: &JsonValue) -> scx::Coordinate {
-        scx::Coordinate::new(
-            json["x"].as_u32().unwrap(),
-            json["y"].as_u32().unwrap()
-        )
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Coordinate, ReviveError> {
+        let x = try!(tile(json, path, "x"));
+        let y = try!(tile(json, path, "y"));
+        if x == NO_TILE || y == NO_TILE {
+            return Ok(scx::Coordinate::new(NO_TILE, NO_TILE));
+        }
+        Ok(scx::Coordinate::new(x, y))
     }
 }
 
 impl JsonRevive for scx::Rectangle {
-    fn from_json(json: &JsonValue) -> scx::Rectangle {
-        let x = json["x1"].as_u32().unwrap();
-        let y = json["y1"].as_u32().unwrap();
-        scx::Rectangle::new(
-            x,
-            y,
-            json["x2"].as_u32().unwrap() - x,
-            json["y2"].as_u32().unwrap() - y
-        )
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Rectangle, ReviveError> {
+        let x1 = try!(tile(json, path, "x1"));
+        let y1 = try!(tile(json, path, "y1"));
+        let x2 = try!(tile(json, path, "x2"));
+        let y2 = try!(tile(json, path, "y2"));
+        if x1 == NO_TILE || y1 == NO_TILE || x2 == NO_TILE || y2 == NO_TILE {
+            // Written as -1 for all four corners.
+            return Ok(scx::Rectangle::new(NO_TILE, NO_TILE, 0, 0));
+        }
+        // Corners may be given in any order.
+        let (x, y) = (x1.min(x2), y1.min(y2));
+        Ok(scx::Rectangle::new(x, y, x1.max(x2) - x, y1.max(y2) - y))
     }
 }
 
 impl JsonRevive for scx::MapTile {
-    fn from_json(json: &JsonValue) -> scx::MapTile {
-        scx::MapTile::new(json["t"].as_u8().unwrap(), json["e"].as_u8().unwrap())
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::MapTile, ReviveError> {
+        Ok(scx::MapTile::new(
+            try!(required(json, path, "t", "a terrain type", JsonValue::as_u8)),
+            try!(required(json, path, "e", "an elevation", JsonValue::as_u8))
+        ))
     }
 }
+
 impl JsonRevive for scx::Map {
-    fn from_json(json: &JsonValue) -> scx::Map {
-        let mut map = scx::Map::new(match json["size"][0].as_u32() {
-            Some(val) => val,
-            None => panic!("Missing map size"),
-        });
-        for row in json["tiles"].members() {
-            for cell in row.members() {
-                map.tiles.push(scx::MapTile::from_json(cell));
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Map, ReviveError> {
+        let size_path = format!("{}[0]", field_path(path, "size"));
+        let size = match json["size"][0].as_u32() {
+            Some(size) => size,
+            None if json["size"][0].is_null() => return Err(ReviveError::Missing(size_path)),
+            None => return Err(ReviveError::InvalidType(size_path, "a map size")),
+        };
+        let mut map = scx::Map::new(size);
+        let tiles_path = field_path(path, "tiles");
+        // The writer expects exactly `size` rows of `size` tiles.
+        if json["tiles"].len() != size as usize {
+            return Err(ReviveError::InvalidType(tiles_path, "one row of tiles for each row of the map"));
+        }
+        for (y, row) in json["tiles"].members().enumerate() {
+            let row_path = format!("{}[{}]", tiles_path, y);
+            if row.len() != size as usize {
+                return Err(ReviveError::InvalidType(row_path, "one tile for each column of the map"));
             }
+            map.tiles.extend(try!(revive_all::<scx::MapTile>(row, &row_path)));
         }
-        map
+        Ok(map)
     }
 }
 
+/// Revive a map, and check that the target version has all of its terrains. Terrain IDs that
+/// `scx::Terrain` does not know, eg. from newer versions or mods, are passed through.
+pub fn revive_map(json: &JsonValue, path: &str, version: scx::ScenVersion) -> Result<scx::Map, ReviveError> {
+    let map = try!(scx::Map::from_json(json, path));
+    for y in 0..map.size {
+        for x in 0..map.size {
+            let terrain = map.terrain_at(x, y).and_then(|id| scx::Terrain::from_u8(id).ok());
+            match terrain {
+                Some(terrain) if !terrain.is_available_in(version) => {
+                    let tile_path = format!("{}[{}][{}].t", field_path(path, "tiles"), y, x);
+                    return Err(ReviveError::InvalidType(tile_path, "a terrain that exists in the target game version"));
+                },
+                _ => (),
+            }
+        }
+    }
+    Ok(map)
+}
+
+// Trigger fields that Lua leaves `nil` are -1, which the game treats as "not set".
 impl JsonRevive for scx::TriggerCondition {
-    fn from_json(json: &JsonValue) -> scx::TriggerCondition {
-        scx::TriggerCondition {
-            condition: json["type"].as_i32().unwrap(),
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::TriggerCondition, ReviveError> {
+        let int = |name| optional(json, path, name, "an integer", -1, JsonValue::as_i32);
+        Ok(scx::TriggerCondition {
+            condition: try!(required(json, path, "type", "a condition type", JsonValue::as_i32)),
             check: 1,
-            amount: json["amount"].as_i32().unwrap(),
-            resource: json["resource"].as_i32().unwrap(),
-            unit_object: json["object_source"].as_i32().unwrap(),
-            unit_location: json["object_location"].as_i32().unwrap(),
-            player: json["player"].as_i32().unwrap(),
-            technology: json["technology"].as_i32().unwrap(),
-            timer: json["time"].as_i32().unwrap(),
-            area: scx::Rectangle::from_json(&json["area"]),
-            unit_group: json["unit_group"].as_i32().unwrap(),
-            unit_type: json["unit_type"].as_i32().unwrap(),
-            ai_signal: json["ai_signal"].as_i32().unwrap(),
-        }
+            amount: try!(int("amount")),
+            resource: try!(int("resource")),
+            unit_object: try!(int("object_source")),
+            unit_location: try!(int("object_location")),
+            player: try!(int("player")),
+            technology: try!(int("technology")),
+            timer: try!(int("time")),
+            area: try!(scx::Rectangle::from_json(&json["area"], &field_path(path, "area"))),
+            unit_group: try!(int("unit_group")),
+            unit_type: try!(int("unit_constant")),
+            object_type: try!(int("unit_type")),
+            ai_signal: try!(int("ai_signal")),
+            extra_fields: vec![],
+            display_order: 0,
+        })
+    }
+}
+
+impl JsonRevive for scx::TriggerEffect {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::TriggerEffect, ReviveError> {
+        let int = |name| optional(json, path, name, "an integer", -1, JsonValue::as_i32);
+        let string = |name| optional(json, path, name, "a string", String::new(), as_string);
+        Ok(scx::TriggerEffect {
+            effect: try!(required(json, path, "type", "an effect type", JsonValue::as_i32)),
+            check: 1,
+            ai_goal: try!(int("goal")),
+            amount: try!(int("amount")),
+            resource: try!(int("resource")),
+            diplomacy: try!(int("diplomacy")),
+            unit_location: try!(int("object_location")),
+            unit_type: try!(int("unit_constant")),
+            player_source: try!(int("player")),
+            player_target: try!(int("player_target")),
+            technology: try!(int("technology")),
+            text_id: try!(int("string_table")),
+            display_time: try!(int("time")),
+            trigger_index: try!(int("trigger_index")),
+            location: try!(scx::Coordinate::from_json(&json["location"], &field_path(path, "location"))),
+            area: try!(scx::Rectangle::from_json(&json["area"], &field_path(path, "area"))),
+            unit_group: try!(int("unit_group")),
+            object_type: try!(int("unit_type")),
+            instruction_panel: try!(int("panel")),
+            extra_fields: vec![],
+            text: try!(string("text")),
+            sound_filename: try!(string("sound")),
+            unit_ids: if json["object_ids"].is_null() {
+                None
+            } else {
+                Some(try!(revive_all(&json["object_ids"], &field_path(path, "object_ids"))))
+            },
+            display_order: 0,
+        })
+    }
+}
+
+impl JsonRevive for scx::Trigger {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Trigger, ReviveError> {
+        let int = |name| optional(json, path, name, "an integer", -1, JsonValue::as_i32);
+        let string = |name| optional(json, path, name, "a string", String::new(), as_string);
+        let flag = |name, default| optional(json, path, name, "a boolean", default, JsonValue::as_bool);
+        Ok(scx::Trigger {
+            enabled: try!(flag("enabled", true)),
+            is_looping: try!(flag("looping", false)),
+            is_objective: try!(flag("objective", false)),
+            objective_order: try!(optional(json, path, "objective_order", "an integer", 0, JsonValue::as_i32)),
+            name: try!(string("name")),
+            description: try!(string("description")),
+            description_id: try!(int("description_id")),
+            short_description: try!(string("short_description")),
+            short_description_id: try!(int("short_description_id")),
+            display_on_screen: try!(flag("display_on_screen", false)),
+            display_order: 0,
+            conditions: try!(revive_all(&json["conditions"], &field_path(path, "conditions"))),
+            effects: try!(revive_all(&json["effects"], &field_path(path, "effects"))),
+        })
+    }
+}
+
+impl JsonRevive for scx::ScenMessages {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::ScenMessages, ReviveError> {
+        let string = |name| optional(json, path, name, "a string", String::new(), as_string);
+        let string_id = |name| optional(json, path, name, "a string ID", 0, JsonValue::as_i32);
+        Ok(scx::ScenMessages {
+            objectives: try!(string("instructions")),
+            hints: try!(string("hints")),
+            scouts: try!(string("scouts")),
+            history: try!(string("history")),
+            victory: try!(string("victory")),
+            loss: try!(string("loss")),
+            objectives_id: try!(string_id("instructions_id")),
+            hints_id: try!(string_id("hints_id")),
+            scouts_id: try!(string_id("scouts_id")),
+            history_id: try!(string_id("history_id")),
+            victory_id: try!(string_id("victory_id")),
+            loss_id: try!(string_id("loss_id")),
+        })
+    }
+}
+
+impl JsonRevive for scx::ScenCinematics {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::ScenCinematics, ReviveError> {
+        let string = |name| optional(json, path, name, "a string", String::new(), as_string);
+        Ok(scx::ScenCinematics {
+            pregame: try!(string("pregame")),
+            victory: try!(string("victory")),
+            loss: try!(string("loss")),
+        })
     }
 }
 
 impl JsonRevive for scx::Unit {
-    fn from_json(json: &JsonValue) -> scx::Unit {
-        scx::Unit {
-            id: json["id"].as_u32().unwrap(),
-            unit_type: unsafe { transmute( json["type"].as_u16().unwrap() ) },
-            x: json["x"].as_f32().unwrap(),
-            y: json["y"].as_f32().unwrap(),
-            angle: json["angle"].as_f32().unwrap(),
-            frame: json["frame"].as_u16().unwrap(),
-            garrison_id: json["garrison_id"].as_u32().unwrap(),
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Unit, ReviveError> {
+        let type_id = try!(required(json, path, "type", "a unit type", JsonValue::as_u16));
+        // Scripts can opt out of the check for objects that `UnitType` does not list.
+        let unit_type = if try!(optional(json, path, "raw", "a boolean", false, JsonValue::as_bool)) {
+            scx::UnitTypeId::Raw(type_id)
+        } else {
+            match scx::UnitType::from_u16(type_id) {
+                Ok(unit_type) => scx::UnitTypeId::Known(unit_type),
+                Err(err) => return Err(ReviveError::UnknownId(field_path(path, "type"), err)),
+            }
+        };
+        Ok(scx::Unit {
+            id: try!(required(json, path, "id", "a unit ID", JsonValue::as_u32)),
+            unit_type: unit_type,
+            x: try!(required(json, path, "x", "a number", JsonValue::as_f32)),
+            y: try!(required(json, path, "y", "a number", JsonValue::as_f32)),
+            angle: try!(optional(json, path, "angle", "a number", 0.0, JsonValue::as_f32)),
+            frame: try!(optional(json, path, "frame", "a frame number", 0, JsonValue::as_u16)),
+            garrison_id: try!(optional(json, path, "garrison_id", "a unit ID", 0, JsonValue::as_u32)),
+        })
+    }
+}
+
+impl JsonRevive for scx::VictoryConditions {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::VictoryConditions, ReviveError> {
+        let int = |name| optional(json, path, name, "an integer", 0, JsonValue::as_i32);
+        let flag = |name| optional(json, path, name, "a boolean", false, JsonValue::as_bool);
+        let mode = try!(optional(json, path, "mode", "a victory mode", String::from("standard"), as_string));
+        match mode.as_ref() {
+            "standard" => Ok(scx::VictoryConditions::Standard),
+            "conquest" => Ok(scx::VictoryConditions::Conquest),
+            "time_limit" => Ok(scx::VictoryConditions::TimeLimit(try!(int("time_limit")))),
+            "score" => Ok(scx::VictoryConditions::Score(try!(int("score")))),
+            "custom" => Ok(scx::VictoryConditions::Custom {
+                conquest: try!(flag("conquest")),
+                relics: try!(int("relics")),
+                exploration: try!(int("exploration")),
+                all: try!(flag("all")),
+            }),
+            _ => Err(ReviveError::InvalidType(field_path(path, "mode"), "a victory mode")),
+        }
+    }
+}
+
+impl JsonRevive for scx::Diplomacy {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Diplomacy, ReviveError> {
+        let teams_path = field_path(path, "teams");
+        let mut teams = vec![];
+        for (i, team) in json["teams"].members().enumerate() {
+            let mut players = vec![];
+            for (j, player) in team.members().enumerate() {
+                match player.as_u8() {
+                    Some(player) => players.push(player),
+                    None => return Err(ReviveError::InvalidType(format!("{}[{}][{}]", teams_path, i, j), "a player number")),
+                }
+            }
+            teams.push(players);
+        }
+        let mut diplomacy = try!(scx::Diplomacy::from_teams(&teams).map_err(|_| {
+            ReviveError::InvalidType(teams_path, "teams of players 1-16, with each player in one team")
+        }));
+        diplomacy.lock_teams = try!(optional(json, path, "lock_teams", "a boolean", false, JsonValue::as_bool));
+        Ok(diplomacy)
+    }
+}
+
+// Resources are either the name of a preset, or a table with amounts. Amounts that are left
+// out use the "low" preset.
+impl JsonRevive for scx::BaseResources {
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::BaseResources, ReviveError> {
+        if json.is_null() {
+            return Ok(scx::BaseResources::default());
+        }
+        if let Some(name) = json.as_str() {
+            return scx::ResourceLevel::from_name(name)
+                .map(scx::BaseResources::preset)
+                .ok_or_else(|| ReviveError::InvalidType(String::from(path), "a resource level"));
+        }
+        if !json.is_object() {
+            return Err(ReviveError::InvalidType(String::from(path), "a resource level or amounts"));
         }
+        let defaults = scx::BaseResources::default();
+        let amount = |name, default| optional(json, path, name, "a resource amount", default, JsonValue::as_u32);
+        Ok(scx::BaseResources {
+            gold: try!(amount("gold", defaults.gold)),
+            wood: try!(amount("wood", defaults.wood)),
+            food: try!(amount("food", defaults.food)),
+            stone: try!(amount("stone", defaults.stone)),
+            ore: try!(amount("ore", defaults.ore)),
+        })
     }
 }
 
 impl JsonRevive for scx::Player {
-    fn from_json(json: &JsonValue) -> scx::Player {
-        scx::Player {
-            name: String::from(json["name"].as_str().unwrap()),
+    fn from_json(json: &JsonValue, path: &str) -> Result<scx::Player, ReviveError> {
+        let civilization = try!(required(json, path, "civilization", "a civilization", JsonValue::as_u8));
+        let civilization = match scx::Civilization::from_u8(civilization) {
+            Ok(civilization) => civilization,
+            Err(err) => return Err(ReviveError::UnknownId(field_path(path, "civilization"), err)),
+        };
+        let name = try!(optional(json, path, "name", "a string", String::new(), as_string));
+        let camera = if json["camera"].is_null() {
+            None
+        } else {
+            let camera_path = field_path(path, "camera");
+            Some((try!(required(&json["camera"], &camera_path, "x", "a number", JsonValue::as_f32)),
+                  try!(required(&json["camera"], &camera_path, "y", "a number", JsonValue::as_f32))))
+        };
+        Ok(scx::Player {
+            editor_name: try!(optional(json, path, "editor_name", "a string", name.clone(), as_string)),
+            name: name,
+            name_id: try!(optional(json, path, "name_id", "a string ID", 0, JsonValue::as_i32)),
             active: 1,
             human: 2,
-            civilization: unsafe { transmute(json["civilization"].as_u8().unwrap()) },
-            resources: scx::BaseResources::default(),
+            civilization: civilization,
+            lock_civilization: false,
+            resources: try!(scx::BaseResources::from_json(&json["resources"], &field_path(path, "resources"))),
+            starting_age: try!(optional(json, path, "starting_age", "a starting age", scx::StartingAge::Dark,
+                                        |value| value.as_str().and_then(scx::StartingAge::from_name))),
+            population_cap: try!(optional(json, path, "population_cap", "a population cap",
+                                          scx::DEFAULT_POPULATION_CAP, JsonValue::as_u32)),
+            camera: camera,
+            color: try!(optional(json, path, "color", "a player colour from 0 to 7", None,
+                                 |value| value.as_u32().and_then(|color| if color < 8 { Some(Some(color)) } else { None }))),
+            ai_name: try!(optional(json, path, "ai_name", "a string", String::new(), as_string)),
+            ai_source: try!(optional(json, path, "ai_source", "a string", String::new(), as_string)),
             units: vec![],
-        }
+            disabled_techs: try!(revive_all(&json["disabled_techs"], &field_path(path, "disabled_techs"))),
+            disabled_units: try!(revive_all(&json["disabled_units"], &field_path(path, "disabled_units"))),
+            disabled_buildings: try!(revive_all(&json["disabled_buildings"], &field_path(path, "disabled_buildings"))),
+        })
+    }
+}
+
+/// Revive diplomacy settings. Allied victory only applies to the first `num_players` players.
+pub fn revive_diplomacy(json: &JsonValue, path: &str, num_players: usize) -> Result<scx::Diplomacy, ReviveError> {
+    let mut diplomacy = try!(scx::Diplomacy::from_json(json, path));
+    let allied_victory = try!(optional(json, path, "allied_victory", "a boolean", false, JsonValue::as_bool));
+    for enabled in diplomacy.allied_victory.iter_mut().take(num_players) {
+        *enabled = allied_victory;
+    }
+    Ok(diplomacy)
+}
+
+/// Revive a player, and check that the target version has its civilization.
+pub fn revive_player(json: &JsonValue, path: &str, version: scx::ScenVersion) -> Result<scx::Player, ReviveError> {
+    let player = try!(scx::Player::from_json(json, path));
+    if !player.civilization.is_available_in(version) {
+        return Err(ReviveError::InvalidType(field_path(path, "civilization"),
+                                            "a civilization that exists in the target game version"));
     }
+    Ok(player)
 }
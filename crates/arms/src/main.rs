@@ -5,22 +5,29 @@ extern crate arms_scx as scx;
 mod json_revive;
 mod scripting;
 
+use std::env;
 use std::io;
 use std::io::prelude::*;
 use std::fs::File;
+use std::path::Path;
+use std::process;
 use std::collections::HashMap;
 
-use json_revive::JsonRevive;
+use json_revive::{JsonRevive, ReviveError, revive_all, revive_diplomacy, revive_map, revive_player};
 use scx::{
+    Bitmap,
     Civilization,
+    Compression,
     UnitType,
-    Map,
     BaseResources,
     Player,
     Unit,
-    ScenHeader,
+    Scenario,
     ScenMessages,
-    ScenImage
+    ScenImage,
+    ScenCinematics,
+    ScenVersion,
+    VictoryConditions
 };
 
 struct PlayerWithNumber {
@@ -28,73 +35,110 @@ struct PlayerWithNumber {
     player: scx::Player
 }
 
-fn test(filename: &str) -> Result<(), io::Error> {
-    let mut f = try!(File::open("Scenario.lua"));
+fn test(filename: &str, version: ScenVersion) -> Result<(), io::Error> {
+    let script = Path::new("Scenario.lua");
+    // Files that the script refers to are relative to the script.
+    let script_dir = script.parent().unwrap_or(Path::new(""));
+    let mut f = try!(File::open(script));
     let mut s = String::new();
     try!(f.read_to_string(&mut s));
-    let result = try!(scripting::run_lua(&s));
-    let mut tree = match json::parse(&result) {
-        Ok(tree) => tree,
-        Err(_) => panic!("Json Error")
-    };
-    let map = Map::from_json(&tree["map"]);
+    let result = try!(scripting::run_lua(&s, version));
+    let tree = try!(json::parse(&result).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)));
+    let map = try!(revive_map(&tree["map"], "map", version));
 
     let mut units: HashMap<i8, Vec<Unit>> = HashMap::new();
-    for player in tree["players"].members() {
-        units.insert(player["number"].as_i8().unwrap(), Vec::new());
+    for (i, player) in tree["players"].members().enumerate() {
+        match player["number"].as_i8() {
+            Some(number) => units.insert(number, Vec::new()),
+            None => return Err(ReviveError::Missing(format!("players[{}].number", i)).into()),
+        };
     }
 
-    for unit in tree["units"].members() {
-        let number = unit["owner"].as_i8().unwrap();
-        match units.get_mut(&number) {
-            Some(player) => player.push(Unit::from_json(unit)),
-            None => (),
+    // Units without an owner belong to gaia.
+    let mut gaia_units = vec![];
+    for (i, unit) in tree["units"].members().enumerate() {
+        let instance = try!(Unit::from_json(unit, &format!("units[{}]", i)));
+        if unit["owner"].is_null() {
+            gaia_units.push(instance);
+            continue;
+        }
+        match unit["owner"].as_i8().and_then(|number| units.get_mut(&number)) {
+            Some(player) => player.push(instance),
+            None => return Err(ReviveError::InvalidType(format!("units[{}].owner", i), "the number of a player").into()),
         };
     }
 
-    let mut players = tree["players"].members()
-        .map(|player| {
-            let number = player["number"].as_i8().unwrap();
-            let instance = scx::Player::from_json(player);
-            match units.remove(&number) {
-                Some(player_units) => instance.with_units(player_units),
-                None => instance
-            }
+    let mut players = vec![];
+    for (i, player) in tree["players"].members().enumerate() {
+        let mut instance = try!(revive_player(player, &format!("players[{}]", i), version));
+        // AI scripts can be loaded from .per files next to the Lua script.
+        if let Some(ai_file) = player["ai_file"].as_str() {
+            instance = try!(instance.with_ai_file(script_dir.join(ai_file)));
+        }
+        players.push(match player["number"].as_i8().and_then(|number| units.remove(&number)) {
+            Some(player_units) => instance.with_units(player_units),
+            None => instance
         });
+    }
 
-    let mut buf = try!(File::create(filename));
+    let diplomacy = try!(revive_diplomacy(&tree["diplomacy"], "diplomacy", players.len()));
+    let victory = try!(VictoryConditions::from_json(&tree["victory"], "victory"));
+    let messages = try!(ScenMessages::from_json(&tree["messages"], "messages"));
+    let cinematics = try!(ScenCinematics::from_json(&tree["cinematics"], "cinematics"));
+    let triggers = try!(revive_all(&tree["triggers"], "triggers"));
 
-    let header = ScenHeader {
-        version: b"1.21",
-        header_type: 2,
-        timestamp: 1451422223,
-        instructions: "Build a fancy-pants base!",
-        filename: filename,
-        players: players.collect(),
-        messages: ScenMessages {
-            objectives: "",
-            hints: "",
-            scouts: "",
-            history: "",
-            victory: "",
-            loss: "",
-        },
-        image: ScenImage {
-            filename: "",
-            included: false,
-            width: 0,
-            height: 0,
-            include: 1,
-        },
-        map: map,
+    // Use a .bmp file as the instructions image, or draw the map into it.
+    let image = &tree["image"];
+    let bitmap = if let Some(path) = image["file"].as_str() {
+        Some(try!(Bitmap::open(script_dir.join(path))))
+    } else if image["minimap"].as_bool() == Some(true) {
+        let width = image["width"].as_u32().unwrap_or(256);
+        let height = image["height"].as_u32().unwrap_or(width / 2);
+        Some(Bitmap::minimap(&map, width, height))
+    } else {
+        None
     };
 
-    buf.write_all(&try!(header.to_bytes())).map(|_| ())
+    let scenario = Scenario::new(version)
+        .with_filename(filename)
+        .with_players(players)
+        .with_diplomacy(diplomacy)
+        .with_victory(victory)
+        .with_messages(messages)
+        .with_cinematics(cinematics)
+        .with_image(ScenImage {
+            // The game only shows the file name, not where the file was loaded from.
+            filename: image["file"].as_str()
+                .and_then(|path| Path::new(path).file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            bitmap: bitmap,
+            include: 1,
+        })
+        .with_map(map)
+        .with_gaia_units(gaia_units)
+        .with_triggers(triggers);
+
+    let mut output = io::BufWriter::new(try!(File::create(filename)));
+    try!(scenario.write_to(&mut output, Compression::Default));
+    output.flush()
 }
 
 fn main() {
-    match test("Test Scenario.scx") {
-        Ok(()) => (),
-        Err(e) => panic!("oops {}", e)
+    // Target game version, eg. `arms 1.18` for Age of Kings or `arms hd` for HD Edition.
+    let version = match env::args().nth(1) {
+        Some(name) => match ScenVersion::from_name(&name) {
+            Some(version) => version,
+            None => {
+                eprintln!("Unknown scenario version {}", name);
+                process::exit(1);
+            },
+        },
+        None => ScenVersion::AoC,
+    };
+    let filename = "Test Scenario.scx";
+    if let Err(e) = test(filename, version) {
+        eprintln!("Could not create {}: {}", filename, e);
+        process::exit(1);
     }
 }
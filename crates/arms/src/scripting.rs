@@ -1,6 +1,7 @@
 use std::io::{Error, ErrorKind};
 use std::result::Result;
 use lua;
+use scx;
 
 fn to_result(status: lua::ThreadStatus) -> Result<(), Error> {
     match status {
@@ -23,12 +24,52 @@ fn load_arms_library(mut state: &mut lua::State) -> Result<(), Error> {
     Ok(())
 }
 
-fn load_prelude(mut state: &mut lua::State) -> Result<(), Error> {
+// Build `Arms.civilizations`, a list of the civilizations that exist in the target version.
+// Each entry has the civilization ID in `id`.
+fn load_civilizations(mut state: &mut lua::State, version: scx::ScenVersion) {
+    state.get_global("Arms");
+    state.new_table();
+    let mut index = 1;
+    let mut id = 1;
+    while let Ok(civilization) = scx::Civilization::from_u8(id) {
+        id += 1;
+        if !civilization.is_available_in(version) {
+            continue;
+        }
+        let info = civilization.info();
+        state.new_table();
+        state.push_integer(civilization as lua::Integer);
+        state.set_field(-2, "id");
+        state.push_string(info.name);
+        state.set_field(-2, "name");
+        state.push_integer(civilization.starting_villagers() as lua::Integer);
+        state.set_field(-2, "starting_villagers");
+        state.push_integer(info.scout as lua::Integer);
+        state.set_field(-2, "scout");
+        state.new_table();
+        for (i, unit_type) in info.unique_units.iter().enumerate() {
+            state.push_integer(*unit_type as lua::Integer);
+            state.raw_seti(-2, i as lua::Integer + 1);
+        }
+        state.set_field(-2, "unique_units");
+        state.raw_seti(-2, index);
+        index += 1;
+    }
+    state.set_field(-2, "civilizations");
+    state.pop(1);
+}
+
+fn load_prelude(mut state: &mut lua::State, version: scx::ScenVersion) -> Result<(), Error> {
+    try!(to_result(state.do_string("Arms = require 'arms'")));
+    load_civilizations(state, version);
     let prelude = "
-        Arms = require 'arms'
         map = Arms.map
+        players = Arms.players
         trigger = Arms.trigger
         messages = Arms.messages
+        victory = Arms.victory
+        cinematics = Arms.cinematics
+        image = Arms.image
         terrain = Arms.terrain
         unit = Arms.unit
         messages = Arms.messages
@@ -64,12 +105,12 @@ fn run_noreturn(mut state: &mut lua::State, text: &str) -> Result<(), Error> {
 }
 
 // Whelp this function looks like a mess! :D
-pub fn run_lua(text: &str) -> Result<String, Error> {
+pub fn run_lua(text: &str, version: scx::ScenVersion) -> Result<String, Error> {
     let mut lua = lua::State::new();
     lua.open_libs(); // TODO don't open os/io/package perhaps
 
     let result = load_arms_library(&mut lua)
-        .and_then(|_| load_prelude(&mut lua))
+        .and_then(|_| load_prelude(&mut lua, version))
         .and_then(|_| load_config(&mut lua, 1, 2))
         .and_then(|_| run_noreturn(&mut lua, text))
         .and_then(|_| to_result(lua.do_string("return require('arms'):to_string()")))
@@ -0,0 +1,45 @@
+extern crate arms_scx as scx;
+
+use std::env;
+use std::io;
+use std::io::prelude::*;
+use std::fs::File;
+use std::process;
+
+fn check(filename: &str) -> Result<Option<scx::Difference>, io::Error> {
+    let mut f = try!(File::open(filename));
+    let mut bytes = vec![];
+    try!(f.read_to_end(&mut bytes));
+    scx::round_trip(&bytes)
+}
+
+fn format_byte(byte: Option<u8>) -> String {
+    match byte {
+        Some(byte) => format!("{:#04x}", byte),
+        None => String::from("EOF"),
+    }
+}
+
+// Read scenario files and write them back out, reporting the first byte where the
+// output differs from the input.
+fn main() {
+    let mut failed = false;
+    for filename in env::args().skip(1) {
+        match check(&filename) {
+            Ok(None) => println!("{}: ok", filename),
+            Ok(Some(diff)) => {
+                failed = true;
+                println!("{}: differs in {:?} at offset {} (expected {}, got {})",
+                         filename, diff.section, diff.offset,
+                         format_byte(diff.expected), format_byte(diff.actual));
+            },
+            Err(e) => {
+                failed = true;
+                println!("{}: error: {}", filename, e);
+            },
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
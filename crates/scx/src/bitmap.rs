@@ -0,0 +1,224 @@
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
+
+use map::Map;
+use terrain::Terrain;
+use util::read_bytes;
+
+const INFO_HEADER_SIZE: u32 = 40;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// An uncompressed bitmap, like the ones that scenarios show on the instructions screen.
+pub struct Bitmap {
+    pub width: i32,
+    /// Positive for bitmaps that store the bottom row first, negative for top-down bitmaps.
+    pub height: i32,
+    pub bit_count: u16,
+    /// Palette entries as blue, green, red, unused.
+    pub palette: Vec<[u8; 4]>,
+    /// Pixel rows, each padded to a multiple of 4 bytes.
+    pub pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    fn stride(width: i32, bit_count: u16) -> usize {
+        (width.abs() as usize * bit_count as usize + 31) / 32 * 4
+    }
+
+    /// Read a .bmp file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Bitmap, io::Error> {
+        let mut file = io::BufReader::new(try!(File::open(path)));
+        let mut magic = [0; 2];
+        try!(file.read_exact(&mut magic));
+        if &magic != b"BM" {
+            return Err(invalid_data(String::from("not a .bmp file")));
+        }
+        // File size, reserved, pixel data offset
+        try!(read_bytes(&mut file, 12));
+        Bitmap::from_reader(&mut file)
+    }
+
+    // A BITMAPINFOHEADER followed by the palette and the pixels, without the .bmp file header.
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<Bitmap, io::Error> {
+        match try!(Bitmap::read_or_skip(input)) {
+            Some(bitmap) => Ok(bitmap),
+            None => Err(invalid_data(String::from("only uncompressed bitmaps with a 40 byte header are supported"))),
+        }
+    }
+
+    /// Like `from_reader`, but skips over bitmaps that `Bitmap` can not represent, like
+    /// compressed ones or ones with a newer header, and returns `None` for them.
+    pub fn read_or_skip<R: Read>(input: &mut R) -> Result<Option<Bitmap>, io::Error> {
+        let header_size = try!(input.read_u32::<LE>());
+        if header_size < INFO_HEADER_SIZE {
+            return Err(invalid_data(format!("unsupported bitmap header size {}", header_size)));
+        }
+        let width = try!(input.read_i32::<LE>());
+        let height = try!(input.read_i32::<LE>());
+        // Planes
+        try!(input.read_u16::<LE>());
+        let bit_count = try!(input.read_u16::<LE>());
+        let compression = try!(input.read_u32::<LE>());
+        let image_size = try!(input.read_u32::<LE>()) as usize;
+        // Horizontal and vertical resolution
+        try!(read_bytes(input, 8));
+        let colors_used = try!(input.read_u32::<LE>()) as usize;
+        // Important colours
+        try!(input.read_u32::<LE>());
+        // Colour masks and colour space of newer headers
+        try!(read_bytes(input, (header_size - INFO_HEADER_SIZE) as usize));
+
+        let palette_size = if colors_used > 0 {
+            colors_used
+        } else if bit_count <= 8 {
+            1 << bit_count
+        } else {
+            0
+        };
+        let mut palette = Vec::with_capacity(palette_size);
+        for _ in 0..palette_size {
+            let mut color = [0; 4];
+            try!(input.read_exact(&mut color));
+            palette.push(color);
+        }
+        // Compressed bitmaps store the size of their pixel data.
+        let pixels_size = if compression == 0 {
+            Bitmap::stride(width, bit_count) * height.abs() as usize
+        } else {
+            image_size
+        };
+        let pixels = try!(read_bytes(input, pixels_size));
+        if compression != 0 || header_size != INFO_HEADER_SIZE {
+            return Ok(None);
+        }
+        Ok(Some(Bitmap {
+            width: width,
+            height: height,
+            bit_count: bit_count,
+            palette: palette,
+            pixels: pixels,
+        }))
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), io::Error> {
+        let expected_size = Bitmap::stride(self.width, self.bit_count) * self.height.abs() as usize;
+        if self.pixels.len() != expected_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("expected {} bytes of pixel data, got {}", expected_size, self.pixels.len())
+            ));
+        }
+        try!(output.write_u32::<LE>(INFO_HEADER_SIZE));
+        try!(output.write_i32::<LE>(self.width));
+        try!(output.write_i32::<LE>(self.height));
+        try!(output.write_u16::<LE>(1));
+        try!(output.write_u16::<LE>(self.bit_count));
+        // Uncompressed
+        try!(output.write_u32::<LE>(0));
+        try!(output.write_u32::<LE>(self.pixels.len() as u32));
+        try!(output.write_i32::<LE>(0));
+        try!(output.write_i32::<LE>(0));
+        try!(output.write_u32::<LE>(self.palette.len() as u32));
+        try!(output.write_u32::<LE>(0));
+        for color in &self.palette {
+            try!(output.write_all(color));
+        }
+        try!(output.write_all(&self.pixels));
+        Ok(())
+    }
+
+    /// Draw the map's terrain like the in-game minimap: a diamond, with tile (0, 0) in the
+    /// left corner.
+    pub fn minimap(map: &Map, width: u32, height: u32) -> Bitmap {
+        // Palette index 0 is the background, the others are terrain IDs + 1.
+        let mut palette = vec![[0, 0, 0, 0]];
+        for id in 0..255 {
+            let (r, g, b) = minimap_color(id);
+            palette.push([b, g, r, 0]);
+        }
+
+        let stride = Bitmap::stride(width as i32, 8);
+        let mut pixels = vec![0; stride * height as usize];
+        let size = map.size as f32;
+        for py in 0..height {
+            let v = (py as f32 + 0.5) / height as f32;
+            for px in 0..width {
+                let u = (px as f32 + 0.5) / width as f32;
+                let x = (u + v - 0.5) * size;
+                let y = (u - v + 0.5) * size;
+                if x < 0.0 || y < 0.0 || x >= size || y >= size {
+                    continue;
+                }
+                if let Some(terrain) = map.terrain_at(x as u32, y as u32) {
+                    // Rows are stored bottom-up.
+                    let row = (height - 1 - py) as usize;
+                    pixels[row * stride + px as usize] = terrain.saturating_add(1);
+                }
+            }
+        }
+
+        Bitmap {
+            width: width as i32,
+            height: height as i32,
+            bit_count: 8,
+            palette: palette,
+            pixels: pixels,
+        }
+    }
+}
+
+fn minimap_color(terrain: u8) -> (u8, u8, u8) {
+    let terrain = match Terrain::from_u8(terrain) {
+        Ok(terrain) => terrain,
+        Err(_) => return (0, 0, 0),
+    };
+    match terrain {
+        Terrain::WaterShallow |
+        Terrain::WaterNotDockable => (48, 93, 182),
+        Terrain::WaterMedium => (40, 80, 164),
+        Terrain::WaterDeep => (32, 64, 140),
+        Terrain::Shallows => (84, 146, 176),
+        Terrain::Beach |
+        Terrain::Desert => (232, 180, 120),
+        Terrain::IceBeach => (200, 190, 170),
+        Terrain::Grass1 |
+        Terrain::Grass2 |
+        Terrain::Grass3 |
+        Terrain::GrassOther => (51, 151, 39),
+        Terrain::Dirt1 |
+        Terrain::Dirt2 |
+        Terrain::Dirt3 |
+        Terrain::Leaves |
+        Terrain::Foundation => (156, 118, 64),
+        Terrain::Farm |
+        Terrain::DeadFarm |
+        Terrain::FarmConstruction1 |
+        Terrain::FarmConstruction2 |
+        Terrain::FarmConstruction3 => (188, 150, 70),
+        Terrain::Forest |
+        Terrain::OakForest |
+        Terrain::PineForest |
+        Terrain::Jungle |
+        Terrain::Bamboo |
+        Terrain::PalmDesert => (0, 100, 20),
+        Terrain::SnowPineForest => (40, 100, 70),
+        Terrain::Road |
+        Terrain::RoadBroken |
+        Terrain::RoadSnow |
+        Terrain::RoadFungus |
+        Terrain::RockRoad => (150, 150, 150),
+        Terrain::WaterBridge => (120, 90, 50),
+        Terrain::Ice |
+        Terrain::Ice2 => (152, 192, 240),
+        Terrain::Snow |
+        Terrain::SnowDirt |
+        Terrain::SnowGrass |
+        Terrain::SnowFoundation => (230, 230, 240),
+    }
+}
@@ -0,0 +1,174 @@
+use consts::{Civilization, UnitType};
+use version::ScenVersion;
+
+/// Game data for a civilization.
+#[derive(Clone, Copy)]
+pub struct CivilizationInfo {
+    pub name: &'static str,
+    /// The first game version that has this civilization, or `None` if only Definitive Edition
+    /// has it.
+    pub since: Option<ScenVersion>,
+    /// Units that only this civilization can train, including their elite upgrades.
+    pub unique_units: &'static [UnitType],
+    /// Villagers on top of the usual three at the start of a game.
+    pub extra_villagers: u32,
+    /// The scout unit at the start of a game.
+    pub scout: UnitType,
+}
+
+fn name(civilization: Civilization) -> &'static str {
+    match civilization {
+        Civilization::None => "Gaia",
+        Civilization::Britons => "Britons",
+        Civilization::Franks => "Franks",
+        Civilization::Goths => "Goths",
+        Civilization::Teutons => "Teutons",
+        Civilization::Japanese => "Japanese",
+        Civilization::Chinese => "Chinese",
+        Civilization::Byzantines => "Byzantines",
+        Civilization::Persians => "Persians",
+        Civilization::Saracens => "Saracens",
+        Civilization::Turks => "Turks",
+        Civilization::Vikings => "Vikings",
+        Civilization::Mongols => "Mongols",
+        Civilization::Celts => "Celts",
+        Civilization::Spanish => "Spanish",
+        Civilization::Aztecs => "Aztecs",
+        Civilization::Mayans => "Mayans",
+        Civilization::Huns => "Huns",
+        Civilization::Koreans => "Koreans",
+        Civilization::Italians => "Italians",
+        Civilization::Indians => "Indians",
+        Civilization::Incas => "Incas",
+        Civilization::Magyars => "Magyars",
+        Civilization::Slavs => "Slavs",
+        Civilization::Portuguese => "Portuguese",
+        Civilization::Ethiopians => "Ethiopians",
+        Civilization::Malians => "Malians",
+        Civilization::Berbers => "Berbers",
+        Civilization::Khmer => "Khmer",
+        Civilization::Malay => "Malay",
+        Civilization::Burmese => "Burmese",
+        Civilization::Vietnamese => "Vietnamese",
+        Civilization::Bulgarians => "Bulgarians",
+        Civilization::Tatars => "Tatars",
+        Civilization::Cumans => "Cumans",
+        Civilization::Lithuanians => "Lithuanians",
+        Civilization::Burgundians => "Burgundians",
+        Civilization::Sicilians => "Sicilians",
+        Civilization::Poles => "Poles",
+        Civilization::Bohemians => "Bohemians",
+        Civilization::Dravidians => "Dravidians",
+        Civilization::Bengalis => "Bengalis",
+        Civilization::Gurjaras => "Gurjaras",
+        Civilization::Romans => "Romans",
+        Civilization::Armenians => "Armenians",
+        Civilization::Georgians => "Georgians",
+    }
+}
+
+fn since(civilization: Civilization) -> Option<ScenVersion> {
+    // Civilization IDs follow the order in which they were added to the game.
+    let id = civilization as u8;
+    if id <= Civilization::Celts as u8 {
+        Some(ScenVersion::AoK)
+    } else if id <= Civilization::Koreans as u8 {
+        Some(ScenVersion::AoC)
+    } else if id <= Civilization::Slavs as u8 {
+        // The Forgotten
+        Some(ScenVersion::HD)
+    } else if id <= Civilization::Vietnamese as u8 {
+        // The African Kingdoms and Rise of the Rajas
+        Some(ScenVersion::HDRajas)
+    } else {
+        None
+    }
+}
+
+// Newer Definitive Edition civilizations' units are not in `UnitType` yet.
+fn unique_units(civilization: Civilization) -> &'static [UnitType] {
+    match civilization {
+        Civilization::Britons => &[UnitType::Longbowman, UnitType::EliteLongbowman],
+        Civilization::Franks => &[UnitType::ThrowingAxeman, UnitType::EliteThrowingAxeman],
+        Civilization::Goths => &[UnitType::Huskarl, UnitType::EliteHuskarl, UnitType::Huskarl2, UnitType::EliteHuskarl2],
+        Civilization::Teutons => &[UnitType::TeutonicKnight, UnitType::EliteTeutonicKnight],
+        Civilization::Japanese => &[UnitType::Samurai, UnitType::EliteSamurai],
+        Civilization::Chinese => &[UnitType::ChuKoNu, UnitType::EliteChuKoNu],
+        Civilization::Byzantines => &[UnitType::Cataphract, UnitType::EliteCataphract],
+        Civilization::Persians => &[UnitType::WarElephant, UnitType::EliteWarElephant],
+        Civilization::Saracens => &[UnitType::Mameluke, UnitType::EliteMameluke],
+        Civilization::Turks => &[UnitType::Janissary, UnitType::EliteJanissary],
+        Civilization::Vikings => &[UnitType::Berserk, UnitType::EliteBerserk, UnitType::Longboat, UnitType::EliteLongboat],
+        Civilization::Mongols => &[UnitType::Mangudai, UnitType::EliteMangudai],
+        Civilization::Celts => &[UnitType::WoadRaider, UnitType::EliteWoadRaider],
+        Civilization::Spanish => &[UnitType::Conquistador, UnitType::EliteConquistador, UnitType::Missionary],
+        Civilization::Aztecs => &[UnitType::JaguarWarrior, UnitType::EliteJaguarWarrior],
+        Civilization::Mayans => &[UnitType::PlumedArcher, UnitType::ElitePlumedArcher],
+        Civilization::Huns => &[UnitType::Tarkan, UnitType::EliteTarkan],
+        Civilization::Koreans => &[UnitType::WarWagon, UnitType::EliteWarWagon, UnitType::TurtleShip, UnitType::EliteTurtleShip],
+        Civilization::Italians => &[UnitType::GenoeseCrossbowman, UnitType::EliteGenoeseCrossbowman, UnitType::Condottiero],
+        Civilization::Indians => &[UnitType::ElephantArcher, UnitType::EliteElephantArcher],
+        Civilization::Incas => &[UnitType::Kamayuk, UnitType::EliteKamayuk, UnitType::Slinger],
+        Civilization::Magyars => &[UnitType::MagyarHuszar, UnitType::EliteMagyarHuszar],
+        Civilization::Slavs => &[UnitType::Boyar, UnitType::EliteBoyar],
+        Civilization::Portuguese => &[UnitType::OrganGun, UnitType::EliteOrganGun, UnitType::Caravel, UnitType::EliteCaravel],
+        Civilization::Ethiopians => &[UnitType::ShotelWarrior, UnitType::EliteShotelWarrior],
+        Civilization::Malians => &[UnitType::Gbeto, UnitType::EliteGbeto],
+        Civilization::Berbers => &[UnitType::CamelArcher, UnitType::EliteCamelArcher, UnitType::Genitour, UnitType::EliteGenitour],
+        Civilization::Khmer => &[UnitType::BallistaElephant, UnitType::EliteBallistaElephant],
+        Civilization::Malay => &[UnitType::KarambitWarrior, UnitType::EliteKarambitWarrior],
+        Civilization::Burmese => &[UnitType::Arambai, UnitType::EliteArambai],
+        Civilization::Vietnamese => &[UnitType::RattanArcher, UnitType::EliteRattanArcher],
+        Civilization::Bulgarians => &[UnitType::Konnik, UnitType::EliteKonnik],
+        Civilization::Tatars => &[UnitType::Keshik, UnitType::EliteKeshik],
+        Civilization::Cumans => &[UnitType::Kipchak, UnitType::EliteKipchak],
+        Civilization::Lithuanians => &[UnitType::Leitis, UnitType::EliteLeitis],
+        _ => &[],
+    }
+}
+
+fn extra_villagers(civilization: Civilization) -> u32 {
+    match civilization {
+        Civilization::Chinese => 3,
+        Civilization::Mayans => 1,
+        _ => 0,
+    }
+}
+
+fn scout(civilization: Civilization) -> UnitType {
+    match civilization {
+        // Mesoamerican civilizations have no stables, so they start with an Eagle Warrior.
+        Civilization::Aztecs |
+        Civilization::Mayans |
+        Civilization::Incas => UnitType::EagleWarrior,
+        _ => UnitType::ScoutCavalry,
+    }
+}
+
+impl Civilization {
+    pub fn info(&self) -> CivilizationInfo {
+        CivilizationInfo {
+            name: name(*self),
+            since: since(*self),
+            unique_units: unique_units(*self),
+            extra_villagers: extra_villagers(*self),
+            scout: scout(*self),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        name(*self)
+    }
+
+    pub fn is_available_in(&self, version: ScenVersion) -> bool {
+        match since(*self) {
+            Some(since) => version.data_version() >= since.data_version(),
+            None => false,
+        }
+    }
+
+    /// The number of villagers this civilization starts a standard game with.
+    pub fn starting_villagers(&self) -> u32 {
+        3 + extra_villagers(*self)
+    }
+}
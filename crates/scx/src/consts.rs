@@ -1,3 +1,32 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// A numeric ID that does not match any of the known constants.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct UnknownIdError {
+    pub kind: &'static str,
+    pub id: u32,
+}
+
+impl fmt::Display for UnknownIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {} {}", self.kind, self.id)
+    }
+}
+
+impl Error for UnknownIdError {
+    fn description(&self) -> &str {
+        "unknown ID"
+    }
+}
+
+impl From<UnknownIdError> for io::Error {
+    fn from(err: UnknownIdError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
 #[allow(dead_code)]
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum Civilization {
@@ -25,11 +54,90 @@ pub enum Civilization {
     Incas,
     Magyars,
     Slavs,
+    // HD Edition
+    Portuguese,
+    Ethiopians,
+    Malians,
+    Berbers,
+    Khmer,
+    Malay,
+    Burmese,
+    Vietnamese,
+    // Definitive Edition
+    Bulgarians,
+    Tatars,
+    Cumans,
+    Lithuanians,
+    Burgundians,
+    Sicilians,
+    Poles,
+    Bohemians,
+    Dravidians,
+    Bengalis,
+    Gurjaras,
+    Romans,
+    Armenians,
+    Georgians,
+}
+
+impl Civilization {
+    pub fn from_u8(id: u8) -> Result<Civilization, UnknownIdError> {
+        match id {
+            0 => Ok(Civilization::None),
+            1 => Ok(Civilization::Britons),
+            2 => Ok(Civilization::Franks),
+            3 => Ok(Civilization::Goths),
+            4 => Ok(Civilization::Teutons),
+            5 => Ok(Civilization::Japanese),
+            6 => Ok(Civilization::Chinese),
+            7 => Ok(Civilization::Byzantines),
+            8 => Ok(Civilization::Persians),
+            9 => Ok(Civilization::Saracens),
+            10 => Ok(Civilization::Turks),
+            11 => Ok(Civilization::Vikings),
+            12 => Ok(Civilization::Mongols),
+            13 => Ok(Civilization::Celts),
+            14 => Ok(Civilization::Spanish),
+            15 => Ok(Civilization::Aztecs),
+            16 => Ok(Civilization::Mayans),
+            17 => Ok(Civilization::Huns),
+            18 => Ok(Civilization::Koreans),
+            19 => Ok(Civilization::Italians),
+            20 => Ok(Civilization::Indians),
+            21 => Ok(Civilization::Incas),
+            22 => Ok(Civilization::Magyars),
+            23 => Ok(Civilization::Slavs),
+            24 => Ok(Civilization::Portuguese),
+            25 => Ok(Civilization::Ethiopians),
+            26 => Ok(Civilization::Malians),
+            27 => Ok(Civilization::Berbers),
+            28 => Ok(Civilization::Khmer),
+            29 => Ok(Civilization::Malay),
+            30 => Ok(Civilization::Burmese),
+            31 => Ok(Civilization::Vietnamese),
+            32 => Ok(Civilization::Bulgarians),
+            33 => Ok(Civilization::Tatars),
+            34 => Ok(Civilization::Cumans),
+            35 => Ok(Civilization::Lithuanians),
+            36 => Ok(Civilization::Burgundians),
+            37 => Ok(Civilization::Sicilians),
+            38 => Ok(Civilization::Poles),
+            39 => Ok(Civilization::Bohemians),
+            40 => Ok(Civilization::Dravidians),
+            41 => Ok(Civilization::Bengalis),
+            42 => Ok(Civilization::Gurjaras),
+            43 => Ok(Civilization::Romans),
+            44 => Ok(Civilization::Armenians),
+            45 => Ok(Civilization::Georgians),
+            _ => Err(UnknownIdError { kind: "civilization", id: id as u32 }),
+        }
+    }
 }
 
 #[allow(dead_code)]
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub enum UnitType {
+    // Units
     Archer = 4,
     HandCannoneer = 5,
     EliteSkirmisher = 6,
@@ -128,40 +236,561 @@ pub enum UnitType {
     EliteWarWagon = 829,
     TurtleShip = 831,
     EliteTurtleShip = 832,
+    // HD Edition unique units
+    Slinger = 185,
+    GenoeseCrossbowman = 866,
+    EliteGenoeseCrossbowman = 868,
+    MagyarHuszar = 869,
+    EliteMagyarHuszar = 871,
+    ElephantArcher = 873,
+    EliteElephantArcher = 875,
+    Boyar = 876,
+    EliteBoyar = 878,
+    Kamayuk = 879,
+    EliteKamayuk = 881,
+    Condottiero = 882,
+    OrganGun = 1001,
+    EliteOrganGun = 1003,
+    Caravel = 1004,
+    EliteCaravel = 1006,
+    CamelArcher = 1007,
+    EliteCamelArcher = 1009,
+    Genitour = 1010,
+    EliteGenitour = 1012,
+    Gbeto = 1013,
+    EliteGbeto = 1015,
+    ShotelWarrior = 1016,
+    EliteShotelWarrior = 1018,
+    BallistaElephant = 1120,
+    EliteBallistaElephant = 1122,
+    KarambitWarrior = 1123,
+    EliteKarambitWarrior = 1125,
+    Arambai = 1126,
+    EliteArambai = 1128,
+    RattanArcher = 1129,
+    EliteRattanArcher = 1131,
+    // Definitive Edition unique units
+    Konnik = 1225,
+    EliteKonnik = 1227,
+    Keshik = 1228,
+    EliteKeshik = 1230,
+    Kipchak = 1231,
+    EliteKipchak = 1233,
+    Leitis = 1234,
+    EliteLeitis = 1236,
+    // Buildings
+    Barracks = 12,
+    Dock = 45,
+    SiegeWorkshop = 49,
+    Farm = 50,
+    Mill = 68,
+    House = 70,
+    PalisadeWall = 72,
+    WatchTower = 79,
+    Castle = 82,
+    Market = 84,
+    ArcheryRange = 87,
+    Stable = 101,
+    Blacksmith = 103,
+    Monastery = 104,
+    TownCenter = 109,
+    StoneWall = 117,
+    FortifiedWall = 155,
+    FishTrap = 199,
+    University = 209,
+    GuardTower = 234,
+    Keep = 235,
+    BombardTower = 236,
+    Wonder = 276,
+    Gate = 487,
+    LumberCamp = 562,
+    MiningCamp = 584,
+    Outpost = 598,
+    // Resources
+    FishPerch = 53,
+    ForageBush = 59,
+    GoldMine = 66,
+    ShoreFish = 69,
+    StoneMine = 102,
+    TreeBamboo = 348,
+    TreeOak = 349,
+    TreePalm = 350,
+    TreePine = 351,
+    GreatFishMarlin = 450,
+    // Animals
+    WildBoar = 48,
+    Deer = 65,
+    Hawk = 96,
+    Wolf = 126,
+    Sheep = 594,
+    Jaguar = 812,
+    Javelina = 822,
+    Turkey = 833,
+    // Relics, flags and cliffs
+    Relic = 285,
+    Cliff1 = 264,
+    Cliff2 = 265,
+    Cliff3 = 266,
+    Cliff4 = 267,
+    Cliff5 = 268,
+    Cliff6 = 269,
+    Cliff7 = 270,
+    Cliff8 = 271,
+    Cliff9 = 272,
+    FlagA = 600,
+    FlagB = 601,
+    FlagC = 602,
+    FlagD = 603,
+    FlagE = 604,
+    // Heroes
+    JoanOfArc = 629,
+    FrankishPaladin = 632,
+    SieurDeMetz = 633,
+    SieurBertrand = 634,
+    DukeDAlencon = 635,
+    LaHire = 636,
+    LordDeGraville = 637,
+    JeanDeLorraine = 638,
+    ConstableRichemont = 639,
+    GuyJosselyne = 640,
+    JeanBureau = 641,
+    SirJohnFastolf = 642,
+    ReynaldDeChatillon = 644,
+    MasterOfTheTemplar = 645,
 }
 
-#[allow(dead_code)]
-#[derive(PartialEq, Eq, Clone, Copy)]
-pub enum Terrain {
-    Grass = 0,
-    Water,
-    Beach,
-    Dirt3,
-    Shallows,
-    Dirt,
-    Farm1,
-    Farm2,
-    Grass3,
-    Forest,
-    Dirt2,
-    Grass2,
-    PalmDesert,
-    SnowForest,
-    Water2,
-    Water3,
-    Road,
-    Road2,
-    Ice,
-    Foundation,
-    FarmCnst1,
-    FarmCnst2,
-    FarmCnst3,
-    Snow,
-    SnowDirt,
-    SnowGrass,
-    SnowRoad,
-    SnowRoad2,
-    KOH,
+impl UnitType {
+    pub fn from_u16(id: u16) -> Result<UnitType, UnknownIdError> {
+        match id {
+            4 => Ok(UnitType::Archer),
+            5 => Ok(UnitType::HandCannoneer),
+            6 => Ok(UnitType::EliteSkirmisher),
+            7 => Ok(UnitType::Skirmisher),
+            8 => Ok(UnitType::Longbowman),
+            11 => Ok(UnitType::Mangudai),
+            12 => Ok(UnitType::Barracks),
+            13 => Ok(UnitType::FishingShip),
+            17 => Ok(UnitType::TradeCog),
+            21 => Ok(UnitType::WarGalley),
+            24 => Ok(UnitType::Crossbowman),
+            25 => Ok(UnitType::TeutonicKnight),
+            35 => Ok(UnitType::BatteringRam),
+            36 => Ok(UnitType::BombardCannon),
+            38 => Ok(UnitType::Knight),
+            39 => Ok(UnitType::CavalryArcher),
+            40 => Ok(UnitType::Cataphract),
+            41 => Ok(UnitType::Huskarl),
+            45 => Ok(UnitType::Dock),
+            46 => Ok(UnitType::Janissary),
+            48 => Ok(UnitType::WildBoar),
+            49 => Ok(UnitType::SiegeWorkshop),
+            50 => Ok(UnitType::Farm),
+            53 => Ok(UnitType::FishPerch),
+            59 => Ok(UnitType::ForageBush),
+            65 => Ok(UnitType::Deer),
+            66 => Ok(UnitType::GoldMine),
+            68 => Ok(UnitType::Mill),
+            69 => Ok(UnitType::ShoreFish),
+            70 => Ok(UnitType::House),
+            72 => Ok(UnitType::PalisadeWall),
+            73 => Ok(UnitType::ChuKoNu),
+            74 => Ok(UnitType::Militia),
+            75 => Ok(UnitType::ManAtArms),
+            76 => Ok(UnitType::HeavySwordsman),
+            77 => Ok(UnitType::LongSwordsman),
+            79 => Ok(UnitType::WatchTower),
+            82 => Ok(UnitType::Castle),
+            83 => Ok(UnitType::Villager),
+            84 => Ok(UnitType::Market),
+            87 => Ok(UnitType::ArcheryRange),
+            93 => Ok(UnitType::Spearman),
+            96 => Ok(UnitType::Hawk),
+            101 => Ok(UnitType::Stable),
+            102 => Ok(UnitType::StoneMine),
+            103 => Ok(UnitType::Blacksmith),
+            104 => Ok(UnitType::Monastery),
+            109 => Ok(UnitType::TownCenter),
+            117 => Ok(UnitType::StoneWall),
+            125 => Ok(UnitType::Monk),
+            126 => Ok(UnitType::Wolf),
+            128 => Ok(UnitType::TradeCart),
+            155 => Ok(UnitType::FortifiedWall),
+            185 => Ok(UnitType::Slinger),
+            199 => Ok(UnitType::FishTrap),
+            209 => Ok(UnitType::University),
+            232 => Ok(UnitType::WoadRaider),
+            234 => Ok(UnitType::GuardTower),
+            235 => Ok(UnitType::Keep),
+            236 => Ok(UnitType::BombardTower),
+            239 => Ok(UnitType::WarElephant),
+            250 => Ok(UnitType::Longboat),
+            264 => Ok(UnitType::Cliff1),
+            265 => Ok(UnitType::Cliff2),
+            266 => Ok(UnitType::Cliff3),
+            267 => Ok(UnitType::Cliff4),
+            268 => Ok(UnitType::Cliff5),
+            269 => Ok(UnitType::Cliff6),
+            270 => Ok(UnitType::Cliff7),
+            271 => Ok(UnitType::Cliff8),
+            272 => Ok(UnitType::Cliff9),
+            276 => Ok(UnitType::Wonder),
+            279 => Ok(UnitType::Scorpion),
+            280 => Ok(UnitType::Mangonel),
+            281 => Ok(UnitType::ThrowingAxeman),
+            282 => Ok(UnitType::Mameluke),
+            283 => Ok(UnitType::Cavalier),
+            285 => Ok(UnitType::Relic),
+            291 => Ok(UnitType::Samurai),
+            329 => Ok(UnitType::Camel),
+            330 => Ok(UnitType::HeavyCamel),
+            331 => Ok(UnitType::Trebuchet),
+            348 => Ok(UnitType::TreeBamboo),
+            349 => Ok(UnitType::TreeOak),
+            350 => Ok(UnitType::TreePalm),
+            351 => Ok(UnitType::TreePine),
+            358 => Ok(UnitType::Pikeman),
+            359 => Ok(UnitType::Halberdier),
+            420 => Ok(UnitType::CannonGalleon),
+            422 => Ok(UnitType::CappedRam),
+            434 => Ok(UnitType::King),
+            440 => Ok(UnitType::Petard),
+            441 => Ok(UnitType::Hussar),
+            442 => Ok(UnitType::Galleon),
+            448 => Ok(UnitType::ScoutCavalry),
+            450 => Ok(UnitType::GreatFishMarlin),
+            473 => Ok(UnitType::TwoHandedSwordsman),
+            474 => Ok(UnitType::HeavyCavalryArcher),
+            487 => Ok(UnitType::Gate),
+            492 => Ok(UnitType::Arbalest),
+            527 => Ok(UnitType::DemolitionShip),
+            528 => Ok(UnitType::HeavyDemolitionShip),
+            529 => Ok(UnitType::FireShip),
+            530 => Ok(UnitType::EliteLongbowman),
+            531 => Ok(UnitType::EliteThrowingAxeman),
+            532 => Ok(UnitType::FastFireShip),
+            533 => Ok(UnitType::EliteLongboat),
+            534 => Ok(UnitType::EliteWoadRaider),
+            539 => Ok(UnitType::Galley),
+            542 => Ok(UnitType::HeavyScorpion),
+            545 => Ok(UnitType::TransportShip),
+            546 => Ok(UnitType::LightCavalry),
+            548 => Ok(UnitType::SiegeRam),
+            550 => Ok(UnitType::Onager),
+            553 => Ok(UnitType::EliteCataphract),
+            554 => Ok(UnitType::EliteTeutonicKnight),
+            555 => Ok(UnitType::EliteHuskarl),
+            556 => Ok(UnitType::EliteMameluke),
+            557 => Ok(UnitType::EliteJanissary),
+            558 => Ok(UnitType::EliteWarElephant),
+            559 => Ok(UnitType::EliteChuKoNu),
+            560 => Ok(UnitType::EliteSamurai),
+            561 => Ok(UnitType::EliteMangudai),
+            562 => Ok(UnitType::LumberCamp),
+            567 => Ok(UnitType::Champion),
+            569 => Ok(UnitType::Paladin),
+            584 => Ok(UnitType::MiningCamp),
+            588 => Ok(UnitType::SiegeOnager),
+            594 => Ok(UnitType::Sheep),
+            598 => Ok(UnitType::Outpost),
+            600 => Ok(UnitType::FlagA),
+            601 => Ok(UnitType::FlagB),
+            602 => Ok(UnitType::FlagC),
+            603 => Ok(UnitType::FlagD),
+            604 => Ok(UnitType::FlagE),
+            629 => Ok(UnitType::JoanOfArc),
+            632 => Ok(UnitType::FrankishPaladin),
+            633 => Ok(UnitType::SieurDeMetz),
+            634 => Ok(UnitType::SieurBertrand),
+            635 => Ok(UnitType::DukeDAlencon),
+            636 => Ok(UnitType::LaHire),
+            637 => Ok(UnitType::LordDeGraville),
+            638 => Ok(UnitType::JeanDeLorraine),
+            639 => Ok(UnitType::ConstableRichemont),
+            640 => Ok(UnitType::GuyJosselyne),
+            641 => Ok(UnitType::JeanBureau),
+            642 => Ok(UnitType::SirJohnFastolf),
+            644 => Ok(UnitType::ReynaldDeChatillon),
+            645 => Ok(UnitType::MasterOfTheTemplar),
+            692 => Ok(UnitType::Berserk),
+            694 => Ok(UnitType::EliteBerserk),
+            725 => Ok(UnitType::JaguarWarrior),
+            726 => Ok(UnitType::EliteJaguarWarrior),
+            751 => Ok(UnitType::EagleWarrior),
+            752 => Ok(UnitType::EliteEagleWarrior),
+            755 => Ok(UnitType::Tarkan),
+            757 => Ok(UnitType::EliteTarkan),
+            759 => Ok(UnitType::Huskarl2),
+            761 => Ok(UnitType::EliteHuskarl2),
+            763 => Ok(UnitType::PlumedArcher),
+            765 => Ok(UnitType::ElitePlumedArcher),
+            771 => Ok(UnitType::Conquistador),
+            773 => Ok(UnitType::EliteConquistador),
+            775 => Ok(UnitType::Missionary),
+            812 => Ok(UnitType::Jaguar),
+            822 => Ok(UnitType::Javelina),
+            827 => Ok(UnitType::WarWagon),
+            829 => Ok(UnitType::EliteWarWagon),
+            831 => Ok(UnitType::TurtleShip),
+            832 => Ok(UnitType::EliteTurtleShip),
+            833 => Ok(UnitType::Turkey),
+            866 => Ok(UnitType::GenoeseCrossbowman),
+            868 => Ok(UnitType::EliteGenoeseCrossbowman),
+            869 => Ok(UnitType::MagyarHuszar),
+            871 => Ok(UnitType::EliteMagyarHuszar),
+            873 => Ok(UnitType::ElephantArcher),
+            875 => Ok(UnitType::EliteElephantArcher),
+            876 => Ok(UnitType::Boyar),
+            878 => Ok(UnitType::EliteBoyar),
+            879 => Ok(UnitType::Kamayuk),
+            881 => Ok(UnitType::EliteKamayuk),
+            882 => Ok(UnitType::Condottiero),
+            1001 => Ok(UnitType::OrganGun),
+            1003 => Ok(UnitType::EliteOrganGun),
+            1004 => Ok(UnitType::Caravel),
+            1006 => Ok(UnitType::EliteCaravel),
+            1007 => Ok(UnitType::CamelArcher),
+            1009 => Ok(UnitType::EliteCamelArcher),
+            1010 => Ok(UnitType::Genitour),
+            1012 => Ok(UnitType::EliteGenitour),
+            1013 => Ok(UnitType::Gbeto),
+            1015 => Ok(UnitType::EliteGbeto),
+            1016 => Ok(UnitType::ShotelWarrior),
+            1018 => Ok(UnitType::EliteShotelWarrior),
+            1120 => Ok(UnitType::BallistaElephant),
+            1122 => Ok(UnitType::EliteBallistaElephant),
+            1123 => Ok(UnitType::KarambitWarrior),
+            1125 => Ok(UnitType::EliteKarambitWarrior),
+            1126 => Ok(UnitType::Arambai),
+            1128 => Ok(UnitType::EliteArambai),
+            1129 => Ok(UnitType::RattanArcher),
+            1131 => Ok(UnitType::EliteRattanArcher),
+            1225 => Ok(UnitType::Konnik),
+            1227 => Ok(UnitType::EliteKonnik),
+            1228 => Ok(UnitType::Keshik),
+            1230 => Ok(UnitType::EliteKeshik),
+            1231 => Ok(UnitType::Kipchak),
+            1233 => Ok(UnitType::EliteKipchak),
+            1234 => Ok(UnitType::Leitis),
+            1236 => Ok(UnitType::EliteLeitis),
+            _ => Err(UnknownIdError { kind: "unit type", id: id as u32 }),
+        }
+    }
+
+    /// The name of this object in the scenario editor.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            UnitType::Archer => "Archer",
+            UnitType::HandCannoneer => "Hand Cannoneer",
+            UnitType::EliteSkirmisher => "Elite Skirmisher",
+            UnitType::Skirmisher => "Skirmisher",
+            UnitType::Longbowman => "Longbowman",
+            UnitType::Mangudai => "Mangudai",
+            UnitType::FishingShip => "Fishing Ship",
+            UnitType::TradeCog => "Trade Cog",
+            UnitType::WarGalley => "War Galley",
+            UnitType::Crossbowman => "Crossbowman",
+            UnitType::TeutonicKnight => "Teutonic Knight",
+            UnitType::BatteringRam => "Battering Ram",
+            UnitType::BombardCannon => "Bombard Cannon",
+            UnitType::Knight => "Knight",
+            UnitType::CavalryArcher => "Cavalry Archer",
+            UnitType::Cataphract => "Cataphract",
+            UnitType::Huskarl => "Huskarl",
+            UnitType::Janissary => "Janissary",
+            UnitType::ChuKoNu => "Chu Ko Nu",
+            UnitType::Militia => "Militia",
+            UnitType::ManAtArms => "Man-at-Arms",
+            UnitType::HeavySwordsman => "Heavy Swordsman",
+            UnitType::LongSwordsman => "Long Swordsman",
+            UnitType::Villager => "Villager",
+            UnitType::Spearman => "Spearman",
+            UnitType::Monk => "Monk",
+            UnitType::TradeCart => "Trade Cart",
+            UnitType::WoadRaider => "Woad Raider",
+            UnitType::WarElephant => "War Elephant",
+            UnitType::Longboat => "Longboat",
+            UnitType::Scorpion => "Scorpion",
+            UnitType::Mangonel => "Mangonel",
+            UnitType::ThrowingAxeman => "Throwing Axeman",
+            UnitType::Mameluke => "Mameluke",
+            UnitType::Cavalier => "Cavalier",
+            UnitType::Samurai => "Samurai",
+            UnitType::Camel => "Camel",
+            UnitType::HeavyCamel => "Heavy Camel",
+            UnitType::Trebuchet => "Trebuchet",
+            UnitType::Pikeman => "Pikeman",
+            UnitType::Halberdier => "Halberdier",
+            UnitType::CannonGalleon => "Cannon Galleon",
+            UnitType::CappedRam => "Capped Ram",
+            UnitType::King => "King",
+            UnitType::Petard => "Petard",
+            UnitType::Hussar => "Hussar",
+            UnitType::Galleon => "Galleon",
+            UnitType::ScoutCavalry => "Scout Cavalry",
+            UnitType::TwoHandedSwordsman => "Two-Handed Swordsman",
+            UnitType::HeavyCavalryArcher => "Heavy Cavalry Archer",
+            UnitType::Arbalest => "Arbalest",
+            UnitType::DemolitionShip => "Demolition Ship",
+            UnitType::HeavyDemolitionShip => "Heavy Demolition Ship",
+            UnitType::FireShip => "Fire Ship",
+            UnitType::EliteLongbowman => "Elite Longbowman",
+            UnitType::EliteThrowingAxeman => "Elite Throwing Axeman",
+            UnitType::FastFireShip => "Fast Fire Ship",
+            UnitType::EliteLongboat => "Elite Longboat",
+            UnitType::EliteWoadRaider => "Elite Woad Raider",
+            UnitType::Galley => "Galley",
+            UnitType::HeavyScorpion => "Heavy Scorpion",
+            UnitType::TransportShip => "Transport Ship",
+            UnitType::LightCavalry => "Light Cavalry",
+            UnitType::SiegeRam => "Siege Ram",
+            UnitType::Onager => "Onager",
+            UnitType::EliteCataphract => "Elite Cataphract",
+            UnitType::EliteTeutonicKnight => "Elite Teutonic Knight",
+            UnitType::EliteHuskarl => "Elite Huskarl",
+            UnitType::EliteMameluke => "Elite Mameluke",
+            UnitType::EliteJanissary => "Elite Janissary",
+            UnitType::EliteWarElephant => "Elite War Elephant",
+            UnitType::EliteChuKoNu => "Elite Chu Ko Nu",
+            UnitType::EliteSamurai => "Elite Samurai",
+            UnitType::EliteMangudai => "Elite Mangudai",
+            UnitType::Champion => "Champion",
+            UnitType::Paladin => "Paladin",
+            UnitType::SiegeOnager => "Siege Onager",
+            UnitType::Berserk => "Berserk",
+            UnitType::EliteBerserk => "Elite Berserk",
+            UnitType::JaguarWarrior => "Jaguar Warrior",
+            UnitType::EliteJaguarWarrior => "Elite Jaguar Warrior",
+            UnitType::EagleWarrior => "Eagle Warrior",
+            UnitType::EliteEagleWarrior => "Elite Eagle Warrior",
+            UnitType::Tarkan => "Tarkan",
+            UnitType::EliteTarkan => "Elite Tarkan",
+            UnitType::Huskarl2 => "Huskarl",
+            UnitType::EliteHuskarl2 => "Elite Huskarl",
+            UnitType::PlumedArcher => "Plumed Archer",
+            UnitType::ElitePlumedArcher => "Elite Plumed Archer",
+            UnitType::Conquistador => "Conquistador",
+            UnitType::EliteConquistador => "Elite Conquistador",
+            UnitType::Missionary => "Missionary",
+            UnitType::WarWagon => "War Wagon",
+            UnitType::EliteWarWagon => "Elite War Wagon",
+            UnitType::TurtleShip => "Turtle Ship",
+            UnitType::EliteTurtleShip => "Elite Turtle Ship",
+            UnitType::Slinger => "Slinger",
+            UnitType::GenoeseCrossbowman => "Genoese Crossbowman",
+            UnitType::EliteGenoeseCrossbowman => "Elite Genoese Crossbowman",
+            UnitType::MagyarHuszar => "Magyar Huszar",
+            UnitType::EliteMagyarHuszar => "Elite Magyar Huszar",
+            UnitType::ElephantArcher => "Elephant Archer",
+            UnitType::EliteElephantArcher => "Elite Elephant Archer",
+            UnitType::Boyar => "Boyar",
+            UnitType::EliteBoyar => "Elite Boyar",
+            UnitType::Kamayuk => "Kamayuk",
+            UnitType::EliteKamayuk => "Elite Kamayuk",
+            UnitType::Condottiero => "Condottiero",
+            UnitType::OrganGun => "Organ Gun",
+            UnitType::EliteOrganGun => "Elite Organ Gun",
+            UnitType::Caravel => "Caravel",
+            UnitType::EliteCaravel => "Elite Caravel",
+            UnitType::CamelArcher => "Camel Archer",
+            UnitType::EliteCamelArcher => "Elite Camel Archer",
+            UnitType::Genitour => "Genitour",
+            UnitType::EliteGenitour => "Elite Genitour",
+            UnitType::Gbeto => "Gbeto",
+            UnitType::EliteGbeto => "Elite Gbeto",
+            UnitType::ShotelWarrior => "Shotel Warrior",
+            UnitType::EliteShotelWarrior => "Elite Shotel Warrior",
+            UnitType::BallistaElephant => "Ballista Elephant",
+            UnitType::EliteBallistaElephant => "Elite Ballista Elephant",
+            UnitType::KarambitWarrior => "Karambit Warrior",
+            UnitType::EliteKarambitWarrior => "Elite Karambit Warrior",
+            UnitType::Arambai => "Arambai",
+            UnitType::EliteArambai => "Elite Arambai",
+            UnitType::RattanArcher => "Rattan Archer",
+            UnitType::EliteRattanArcher => "Elite Rattan Archer",
+            UnitType::Konnik => "Konnik",
+            UnitType::EliteKonnik => "Elite Konnik",
+            UnitType::Keshik => "Keshik",
+            UnitType::EliteKeshik => "Elite Keshik",
+            UnitType::Kipchak => "Kipchak",
+            UnitType::EliteKipchak => "Elite Kipchak",
+            UnitType::Leitis => "Leitis",
+            UnitType::EliteLeitis => "Elite Leitis",
+            UnitType::Barracks => "Barracks",
+            UnitType::Dock => "Dock",
+            UnitType::SiegeWorkshop => "Siege Workshop",
+            UnitType::Farm => "Farm",
+            UnitType::Mill => "Mill",
+            UnitType::House => "House",
+            UnitType::PalisadeWall => "Palisade Wall",
+            UnitType::WatchTower => "Watch Tower",
+            UnitType::Castle => "Castle",
+            UnitType::Market => "Market",
+            UnitType::ArcheryRange => "Archery Range",
+            UnitType::Stable => "Stable",
+            UnitType::Blacksmith => "Blacksmith",
+            UnitType::Monastery => "Monastery",
+            UnitType::TownCenter => "Town Center",
+            UnitType::StoneWall => "Stone Wall",
+            UnitType::FortifiedWall => "Fortified Wall",
+            UnitType::FishTrap => "Fish Trap",
+            UnitType::University => "University",
+            UnitType::GuardTower => "Guard Tower",
+            UnitType::Keep => "Keep",
+            UnitType::BombardTower => "Bombard Tower",
+            UnitType::Wonder => "Wonder",
+            UnitType::Gate => "Gate",
+            UnitType::LumberCamp => "Lumber Camp",
+            UnitType::MiningCamp => "Mining Camp",
+            UnitType::Outpost => "Outpost",
+            UnitType::FishPerch => "Fish (Perch)",
+            UnitType::ForageBush => "Forage Bush",
+            UnitType::GoldMine => "Gold Mine",
+            UnitType::ShoreFish => "Shore Fish",
+            UnitType::StoneMine => "Stone Mine",
+            UnitType::TreeBamboo => "Tree (Bamboo Forest)",
+            UnitType::TreeOak => "Tree (Oak Forest)",
+            UnitType::TreePalm => "Tree (Palm Forest)",
+            UnitType::TreePine => "Tree (Pine Forest)",
+            UnitType::GreatFishMarlin => "Great Fish (Marlin)",
+            UnitType::WildBoar => "Wild Boar",
+            UnitType::Deer => "Deer",
+            UnitType::Hawk => "Hawk",
+            UnitType::Wolf => "Wolf",
+            UnitType::Sheep => "Sheep",
+            UnitType::Jaguar => "Jaguar",
+            UnitType::Javelina => "Javelina",
+            UnitType::Turkey => "Turkey",
+            UnitType::Relic => "Relic",
+            UnitType::Cliff1 => "Cliff 1",
+            UnitType::Cliff2 => "Cliff 2",
+            UnitType::Cliff3 => "Cliff 3",
+            UnitType::Cliff4 => "Cliff 4",
+            UnitType::Cliff5 => "Cliff 5",
+            UnitType::Cliff6 => "Cliff 6",
+            UnitType::Cliff7 => "Cliff 7",
+            UnitType::Cliff8 => "Cliff 8",
+            UnitType::Cliff9 => "Cliff 9",
+            UnitType::FlagA => "Flag A",
+            UnitType::FlagB => "Flag B",
+            UnitType::FlagC => "Flag C",
+            UnitType::FlagD => "Flag D",
+            UnitType::FlagE => "Flag E",
+            UnitType::JoanOfArc => "Joan of Arc",
+            UnitType::FrankishPaladin => "Frankish Paladin",
+            UnitType::SieurDeMetz => "Sieur de Metz",
+            UnitType::SieurBertrand => "Sieur Bertrand",
+            UnitType::DukeDAlencon => "Duke D'Alencon",
+            UnitType::LaHire => "La Hire",
+            UnitType::LordDeGraville => "Lord de Graville",
+            UnitType::JeanDeLorraine => "Jean de Lorraine",
+            UnitType::ConstableRichemont => "Constable Richemont",
+            UnitType::GuyJosselyne => "Guy Josselyne",
+            UnitType::JeanBureau => "Jean Bureau",
+            UnitType::SirJohnFastolf => "Sir John Fastolf",
+            UnitType::ReynaldDeChatillon => "Reynald de Chatillon",
+            UnitType::MasterOfTheTemplar => "Master of the Templar",
+        }
+    }
 }
 
 #[allow(dead_code)]
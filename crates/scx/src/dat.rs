@@ -0,0 +1,449 @@
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+use byteorder::{LittleEndian as LE, ReadBytesExt};
+use flate2::read::DeflateDecoder;
+
+use util::{read_fixed_str, skip};
+
+// Only The Conquerors' empires2_x1_p1.dat is supported.
+const DAT_VERSION: &'static [u8] = b"VER 5.7";
+// Number of terrain slots, including unused ones.
+const TERRAIN_SLOTS: usize = 42;
+const TERRAIN_BORDERS: usize = 16;
+// Number of tile shapes, eg. flat, slopes.
+const TILE_TYPES: usize = 19;
+
+// Genie unit types. Each type up to buildings stores the fields of all types before it. Trees
+// (90) only have the common fields.
+const UNIT_FLAG: u8 = 20;
+const UNIT_DEAD_FISH: u8 = 30;
+const UNIT_BIRD: u8 = 40;
+const UNIT_COMBATANT: u8 = 50;
+const UNIT_PROJECTILE: u8 = 60;
+const UNIT_CREATABLE: u8 = 70;
+const UNIT_BUILDING: u8 = 80;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A terrain type from the .dat file.
+pub struct DatTerrain {
+    pub id: u8,
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// A unit, building or other object from the .dat file.
+pub struct DatUnit {
+    pub id: u16,
+    pub name: String,
+    /// Genie unit type, eg. 70 for units that can be trained, 80 for buildings.
+    pub kind: u8,
+    pub class: i16,
+    pub hit_points: i16,
+    /// Half of the width and height of the object, in tiles.
+    pub collision_size: (f32, f32),
+    pub hide_in_editor: bool,
+}
+
+/// A civilization, with its own copy of all units.
+pub struct DatCivilization {
+    pub id: u8,
+    pub name: String,
+    /// The technology that disables the units and techs this civilization can not use.
+    pub tech_tree: i16,
+    pub team_bonus: i16,
+    pub units: Vec<DatUnit>,
+}
+
+/// A technology from the .dat file.
+pub struct DatTech {
+    pub id: u16,
+    pub name: String,
+    pub required_techs: Vec<i16>,
+    /// Number of `required_techs` that must be researched first.
+    pub required_tech_count: i16,
+    /// The only civilization that can research this tech, or -1.
+    pub civilization: i16,
+    /// The building that researches this tech, or -1.
+    pub research_location: i16,
+    pub research_time: i16,
+}
+
+/// Game data from a genie .dat file, eg. empires2_x1_p1.dat.
+///
+/// The layout follows the community documentation of the format. The tests only use a
+/// generated file, so the reader has not been checked against the game's own data file.
+pub struct DatFile {
+    pub terrains: Vec<DatTerrain>,
+    pub civilizations: Vec<DatCivilization>,
+    pub techs: Vec<DatTech>,
+}
+
+impl DatUnit {
+    pub fn is_building(&self) -> bool {
+        self.kind == UNIT_BUILDING
+    }
+
+    /// Size in tiles, as (width, height).
+    pub fn footprint(&self) -> (u32, u32) {
+        let (x, y) = self.collision_size;
+        ((x * 2.0).round().max(1.0) as u32, (y * 2.0).round().max(1.0) as u32)
+    }
+
+    fn from_reader<R: Read>(input: &mut R) -> Result<DatUnit, io::Error> {
+        let kind = try!(input.read_u8());
+        let name_length = try!(input.read_u16::<LE>()) as usize;
+        let id = try!(input.read_u16::<LE>());
+        // Language DLL name, creation string
+        try!(skip(input, 4));
+        let class = try!(input.read_i16::<LE>());
+        // Standing graphics, dying graphics, undead mode
+        try!(skip(input, 9));
+        let hit_points = try!(input.read_i16::<LE>());
+        // Line of sight, garrison capacity
+        try!(skip(input, 5));
+        let collision_x = try!(input.read_f32::<LE>());
+        let collision_y = try!(input.read_f32::<LE>());
+        // Collision z, train sound, damage sound, dead unit, sort number, can be built on,
+        // icon
+        try!(skip(input, 14));
+        let hide_in_editor = try!(input.read_u8()) != 0;
+        // Portrait, enabled, disabled, placement terrains, clearance size, hill mode,
+        // fog visibility, terrain restriction, fly mode, resource capacity, resource decay,
+        // blast defense, combat level, interaction mode, minimap mode, interface kind,
+        // multiple attribute mode, minimap colour
+        try!(skip(input, 41));
+        // Help string, hotkey string, hotkey, recyclable, auto gather, doppelganger on death,
+        // gather group, occlusion mode, obstruction type, obstruction class, trait,
+        // civilization, ???
+        try!(skip(input, 23));
+        // Selection effect, editor selection colour, selection shape size
+        try!(skip(input, 14));
+        // Resource storages
+        try!(skip(input, 3 * 7));
+        let num_damage_graphics = try!(input.read_u8()) as usize;
+        try!(skip(input, num_damage_graphics * 5));
+        // Selection sound, dying sound, attack reaction, convert terrain
+        try!(skip(input, 6));
+        let name = try!(read_fixed_str(input, name_length));
+        // Copy ID, base ID
+        try!(skip(input, 4));
+
+        if kind >= UNIT_FLAG && kind <= UNIT_BUILDING {
+            // Speed
+            try!(skip(input, 4));
+        }
+        if kind >= UNIT_DEAD_FISH && kind <= UNIT_BUILDING {
+            // Walking and running graphics, rotation speed, size class, tracking unit,
+            // tracking mode, tracking density, move algorithm, turn radius, yaw
+            try!(skip(input, 4 + 4 + 1 + 2 + 1 + 4 + 1 + 5 * 4));
+        }
+        if kind >= UNIT_BIRD && kind <= UNIT_BUILDING {
+            // Default task, search radius, work rate, drop sites, task swap group, attack
+            // sound, move sound, run pattern
+            try!(skip(input, 2 + 4 + 4 + 4 + 1 + 2 + 2 + 1));
+        }
+        if kind >= UNIT_COMBATANT && kind <= UNIT_BUILDING {
+            // Base armour
+            try!(skip(input, 2));
+            let num_attacks = try!(input.read_u16::<LE>()) as usize;
+            try!(skip(input, num_attacks * 4));
+            let num_armours = try!(input.read_u16::<LE>()) as usize;
+            try!(skip(input, num_armours * 4));
+            // Terrain defense bonus, max range, blast width, reload time, projectile,
+            // accuracy, break off combat, frame delay, graphic displacement, blast level,
+            // min range, accuracy dispersion, attack graphic, displayed armour, attack, range,
+            // reload time
+            try!(skip(input, 2 + 4 + 4 + 4 + 2 + 2 + 1 + 2 + 12 + 1 + 4 + 4 + 2 + 2 + 2 + 4 + 4));
+        }
+        if kind == UNIT_PROJECTILE {
+            // Projectile type, smart mode, hit mode, vanish mode, area effects, arc
+            try!(skip(input, 5 + 4));
+        }
+        if kind >= UNIT_CREATABLE && kind <= UNIT_BUILDING {
+            // Costs, train time, train location, button
+            try!(skip(input, 3 * 6 + 2 + 2 + 1));
+            // Rear and flank attack modifiers, creatable type, hero mode, garrison graphic,
+            // projectile counts, spawning area, secondary projectile, special graphic,
+            // special ability, displayed pierce armour
+            try!(skip(input, 4 + 4 + 1 + 1 + 4 + 4 + 1 + 12 + 4 + 4 + 1 + 2));
+        }
+        if kind == UNIT_BUILDING {
+            // Construction and snow graphics, adjacent mode, graphics angle, disappears when
+            // built, stack unit, foundation terrain, overlay, research
+            try!(skip(input, 2 + 2 + 1 + 2 + 1 + 2 + 2 + 2 + 2));
+            // Can burn, annexes, head unit, transform unit, transform sound, construction
+            // sound, garrison type, heal rate, repair rate, pile unit, looting table
+            try!(skip(input, 1 + 4 * 10 + 2 + 2 + 2 + 2 + 1 + 4 + 4 + 2 + 6));
+        }
+
+        Ok(DatUnit {
+            id: id,
+            name: name,
+            kind: kind,
+            class: class,
+            hit_points: hit_points,
+            collision_size: (collision_x, collision_y),
+            hide_in_editor: hide_in_editor,
+        })
+    }
+}
+
+impl DatCivilization {
+    fn from_reader<R: Read>(input: &mut R, id: u8) -> Result<DatCivilization, io::Error> {
+        // Player type
+        try!(input.read_u8());
+        let name = try!(read_fixed_str(input, 20));
+        let num_resources = try!(input.read_u16::<LE>()) as usize;
+        let tech_tree = try!(input.read_i16::<LE>());
+        let team_bonus = try!(input.read_i16::<LE>());
+        // Starting resources
+        try!(skip(input, num_resources * 4));
+        // Icon set
+        try!(input.read_u8());
+        let num_units = try!(input.read_u16::<LE>()) as usize;
+        let mut pointers = vec![];
+        for _ in 0..num_units {
+            pointers.push(try!(input.read_i32::<LE>()));
+        }
+        let mut units = vec![];
+        for pointer in pointers {
+            if pointer != 0 {
+                units.push(try!(DatUnit::from_reader(input)));
+            }
+        }
+        Ok(DatCivilization {
+            id: id,
+            name: name,
+            tech_tree: tech_tree,
+            team_bonus: team_bonus,
+            units: units,
+        })
+    }
+
+    pub fn unit(&self, id: u16) -> Option<&DatUnit> {
+        self.units.iter().find(|unit| unit.id == id)
+    }
+}
+
+impl DatTech {
+    fn from_reader<R: Read>(input: &mut R, id: u16) -> Result<DatTech, io::Error> {
+        let mut required_techs = vec![];
+        for _ in 0..6 {
+            let tech = try!(input.read_i16::<LE>());
+            if tech >= 0 {
+                required_techs.push(tech);
+            }
+        }
+        // Costs
+        try!(skip(input, 3 * 5));
+        let required_tech_count = try!(input.read_i16::<LE>());
+        let civilization = try!(input.read_i16::<LE>());
+        // Full tech mode
+        try!(input.read_i16::<LE>());
+        let research_location = try!(input.read_i16::<LE>());
+        // Language DLL name, description
+        try!(skip(input, 4));
+        let research_time = try!(input.read_i16::<LE>());
+        // Effect, type, icon, button, help string, tech tree string, hotkey
+        try!(skip(input, 2 + 2 + 2 + 1 + 4 + 4 + 4));
+        let name_length = try!(input.read_u16::<LE>()) as usize;
+        let name = try!(read_fixed_str(input, name_length));
+        Ok(DatTech {
+            id: id,
+            name: name,
+            required_techs: required_techs,
+            required_tech_count: required_tech_count,
+            civilization: civilization,
+            research_location: research_location,
+            research_time: research_time,
+        })
+    }
+}
+
+fn skip_graphic<R: Read>(input: &mut R) -> Result<(), io::Error> {
+    // Name, file name, SLP, loaded, colour flag, layer, player colour, rainbow,
+    // transparent selection, coordinates
+    try!(skip(input, 21 + 13 + 4 + 6 + 8));
+    let num_deltas = try!(input.read_u16::<LE>()) as usize;
+    // Sound
+    try!(skip(input, 2));
+    let has_angle_sounds = try!(input.read_u8()) != 0;
+    // Frame count
+    try!(skip(input, 2));
+    let num_angles = try!(input.read_u16::<LE>()) as usize;
+    // Speed, frame duration, replay delay, sequence type, ID, mirroring mode, editor flag
+    try!(skip(input, 4 + 4 + 4 + 1 + 2 + 1 + 1));
+    try!(skip(input, num_deltas * 16));
+    if has_angle_sounds {
+        try!(skip(input, num_angles * 12));
+    }
+    Ok(())
+}
+
+fn read_terrain<R: Read>(input: &mut R, id: u8) -> Result<DatTerrain, io::Error> {
+    let enabled = try!(input.read_u8()) != 0;
+    // Random
+    try!(input.read_u8());
+    let name = try!(read_fixed_str(input, 13));
+    // Internal name, SLP, shape pointer, sound, blend priority, blend type, colours, cliff
+    // colours, passable and impassable terrain, animation
+    try!(skip(input, 13 + 4 + 4 + 4 + 4 + 4 + 3 + 2 + 2 + 1 + 2 + 2 + 4 + 4 + 2 + 2 + 4 + 1 + 1));
+    // Elevation graphics
+    try!(skip(input, TILE_TYPES * 6));
+    // Terrain to draw, dimensions, borders, terrain units, unit density, unit centering,
+    // number of terrain units, phantom
+    try!(skip(input, 2 + 4 + TERRAIN_SLOTS * 2 + 30 * 2 + 30 * 2 + 30 + 2 + 2));
+    Ok(DatTerrain {
+        id: id,
+        name: name,
+        enabled: enabled,
+    })
+}
+
+fn skip_terrain_border<R: Read>(input: &mut R) -> Result<(), io::Error> {
+    // Enabled, random, names, SLP, shape pointer, sound, colours, animation
+    try!(skip(input, 2 + 13 + 13 + 4 + 4 + 4 + 3 + 1 + 2 + 2 + 4 + 4 + 2 + 2 + 4 + 1 + 1));
+    // Border graphics
+    try!(skip(input, TILE_TYPES * 12 * 6));
+    // Draw tile, underlay terrain, border style
+    skip(input, 6)
+}
+
+fn read_terrain_block<R: Read>(input: &mut R) -> Result<Vec<DatTerrain>, io::Error> {
+    // Pointers, map and world sizes, tile sizes, padding
+    try!(skip(input, 6 * 4 + TILE_TYPES * 6 + 2));
+    let mut terrains = vec![];
+    for id in 0..TERRAIN_SLOTS as u8 {
+        terrains.push(try!(read_terrain(input, id)));
+    }
+    for _ in 0..TERRAIN_BORDERS {
+        try!(skip_terrain_border(input));
+    }
+    // Map row offset, map bounds, terrains and borders used, max terrain, tile sizes,
+    // elevation height, current row and column, block bounds, search map pointers,
+    // frame change, visibility and fog flags
+    try!(skip(input, 4 + 6 * 4 + 2 + 2 + 2 + 5 * 2 + 6 * 2 + 4 + 4 + 1 + 1 + 1));
+    // ???
+    try!(skip(input, 21 + 157 * 4));
+    Ok(terrains)
+}
+
+fn skip_random_maps<R: Read>(input: &mut R) -> Result<(), io::Error> {
+    let num_maps = try!(input.read_u32::<LE>()) as usize;
+    // Pointer
+    try!(skip(input, 4));
+    // Map headers
+    try!(skip(input, num_maps * 18 * 4));
+    for _ in 0..num_maps {
+        // ID, borders, border usage, water shape, base terrain, land coverage, ???
+        try!(skip(input, 10 * 4));
+        // Lands, terrains, units and elevations: each a count and a pointer, followed by
+        // the list
+        for &size in &[44, 24, 44, 24] {
+            let count = try!(input.read_u32::<LE>()) as usize;
+            try!(skip(input, 4));
+            try!(skip(input, count * size));
+        }
+    }
+    Ok(())
+}
+
+impl DatFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<DatFile, io::Error> {
+        DatFile::from_reader(try!(File::open(path)))
+    }
+
+    /// Read a compressed .dat file.
+    pub fn from_reader<R: Read>(input: R) -> Result<DatFile, io::Error> {
+        let mut input = DeflateDecoder::new(input);
+        let input = &mut input;
+
+        let version = try!(read_fixed_str(input, 8));
+        if version.as_bytes() != DAT_VERSION {
+            return Err(invalid_data(format!("unsupported .dat version {:?}", version)));
+        }
+
+        let num_restrictions = try!(input.read_u16::<LE>()) as usize;
+        let num_terrains = try!(input.read_u16::<LE>()) as usize;
+        // Restriction pointers, pass graphic pointers
+        try!(skip(input, num_restrictions * 2 * 4));
+        // Passability and pass graphics for each terrain
+        try!(skip(input, num_restrictions * num_terrains * (4 + 16)));
+
+        let num_colours = try!(input.read_u16::<LE>()) as usize;
+        try!(skip(input, num_colours * 36));
+
+        let num_sounds = try!(input.read_u16::<LE>()) as usize;
+        for _ in 0..num_sounds {
+            // ID, play delay
+            try!(skip(input, 4));
+            let num_files = try!(input.read_u16::<LE>()) as usize;
+            // Cache time
+            try!(skip(input, 4));
+            try!(skip(input, num_files * 23));
+        }
+
+        let num_graphics = try!(input.read_u16::<LE>()) as usize;
+        let mut graphic_pointers = vec![];
+        for _ in 0..num_graphics {
+            graphic_pointers.push(try!(input.read_i32::<LE>()));
+        }
+        for pointer in graphic_pointers {
+            if pointer != 0 {
+                try!(skip_graphic(input));
+            }
+        }
+
+        let terrains = try!(read_terrain_block(input));
+        try!(skip_random_maps(input));
+
+        // Technology effects
+        let num_effects = try!(input.read_u32::<LE>()) as usize;
+        for _ in 0..num_effects {
+            // Name
+            try!(skip(input, 31));
+            let num_commands = try!(input.read_u16::<LE>()) as usize;
+            try!(skip(input, num_commands * 11));
+        }
+
+        // Unit tasks
+        let num_unit_headers = try!(input.read_u32::<LE>()) as usize;
+        for _ in 0..num_unit_headers {
+            if try!(input.read_u8()) != 0 {
+                let num_tasks = try!(input.read_u16::<LE>()) as usize;
+                try!(skip(input, num_tasks * 59));
+            }
+        }
+
+        let num_civilizations = try!(input.read_u16::<LE>()) as usize;
+        if num_civilizations > 256 {
+            return Err(invalid_data(format!("too many civilizations ({})", num_civilizations)));
+        }
+        let mut civilizations = vec![];
+        for id in 0..num_civilizations {
+            civilizations.push(try!(DatCivilization::from_reader(input, id as u8)));
+        }
+
+        let num_techs = try!(input.read_u16::<LE>()) as usize;
+        let mut techs = vec![];
+        for id in 0..num_techs {
+            techs.push(try!(DatTech::from_reader(input, id as u16)));
+        }
+
+        Ok(DatFile {
+            terrains: terrains,
+            civilizations: civilizations,
+            techs: techs,
+        })
+    }
+
+    /// Look up a unit. Uses Gaia's copy, which contains every unit.
+    pub fn unit(&self, id: u16) -> Option<&DatUnit> {
+        self.civilizations.first().and_then(|gaia| gaia.unit(id))
+    }
+}
@@ -0,0 +1,96 @@
+use std::io;
+
+/// How one player treats another.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DiplomacyStance {
+    Allied = 0,
+    Neutral = 1,
+    Enemy = 3,
+}
+
+impl DiplomacyStance {
+    pub fn from_i32(id: i32) -> Option<DiplomacyStance> {
+        match id {
+            0 => Some(DiplomacyStance::Allied),
+            1 => Some(DiplomacyStance::Neutral),
+            3 => Some(DiplomacyStance::Enemy),
+            _ => None,
+        }
+    }
+
+    // The per-player diplomacy that the AI uses has its own numbering.
+    pub fn ai_stance(&self) -> i32 {
+        match *self {
+            DiplomacyStance::Allied => 2,
+            DiplomacyStance::Neutral => 3,
+            DiplomacyStance::Enemy => 4,
+        }
+    }
+}
+
+/// Diplomacy between the 16 player slots. Player numbers are 1-based, like in the game.
+pub struct Diplomacy {
+    stances: [[DiplomacyStance; 16]; 16],
+    pub allied_victory: [bool; 16],
+    /// Whether players can change their team in the lobby. Only Definitive Edition stores this,
+    /// so writing fails if it is set.
+    pub lock_teams: bool,
+}
+
+impl Diplomacy {
+    /// Every player is an enemy of every other player.
+    pub fn new() -> Diplomacy {
+        let mut stances = [[DiplomacyStance::Enemy; 16]; 16];
+        for i in 0..16 {
+            stances[i][i] = DiplomacyStance::Allied;
+        }
+        Diplomacy {
+            stances: stances,
+            allied_victory: [false; 16],
+            lock_teams: false,
+        }
+    }
+
+    /// Players in the same team are allies, and enemies of everyone else. Players that are
+    /// not in any team play on their own.
+    pub fn from_teams(teams: &[Vec<u8>]) -> Result<Diplomacy, io::Error> {
+        let mut diplomacy = Diplomacy::new();
+        let mut seen = [false; 16];
+        for team in teams {
+            for &player in team {
+                let index = try!(Diplomacy::check_player(player));
+                if seen[index] {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("player {} is in more than one team", player)
+                    ));
+                }
+                seen[index] = true;
+                for &other in team {
+                    try!(diplomacy.set_stance(player, other, DiplomacyStance::Allied));
+                }
+            }
+        }
+        Ok(diplomacy)
+    }
+
+    fn check_player(player: u8) -> Result<usize, io::Error> {
+        if player < 1 || player > 16 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("invalid player {}", player)));
+        }
+        Ok(player as usize - 1)
+    }
+
+    /// How `player` treats `other`. Panics if either is not in 1-16.
+    pub fn stance(&self, player: u8, other: u8) -> DiplomacyStance {
+        self.stances[player as usize - 1][other as usize - 1]
+    }
+
+    /// Set how `player` treats `other`. Stances do not have to be mutual.
+    pub fn set_stance(&mut self, player: u8, other: u8, stance: DiplomacyStance) -> Result<(), io::Error> {
+        let a = try!(Diplomacy::check_player(player));
+        let b = try!(Diplomacy::check_player(other));
+        self.stances[a][b] = stance;
+        Ok(())
+    }
+}
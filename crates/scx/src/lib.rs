@@ -1,18 +1,40 @@
 extern crate byteorder;
 extern crate flate2;
 
+mod bitmap;
+mod civ_info;
 mod consts;
+mod dat;
+mod diplomacy;
 mod map;
 mod player;
+mod roundtrip;
 mod selection;
 mod scenario;
+mod tech;
+mod terrain;
 mod trigger;
 mod unit;
+mod unit_info;
+mod util;
+mod version;
+mod victory;
 
-pub use consts::{Civilization, UnitType, Terrain, MapSize};
+pub use bitmap::Bitmap;
+pub use flate2::Compression;
+pub use civ_info::CivilizationInfo;
+pub use consts::{Civilization, UnitType, MapSize, UnknownIdError};
+pub use dat::{DatFile, DatCivilization, DatTech, DatTerrain, DatUnit};
+pub use diplomacy::{Diplomacy, DiplomacyStance};
 pub use map::{Map, MapTile};
-pub use player::{BaseResources, Player};
+pub use player::{BaseResources, Player, ResourceLevel, StartingAge, DEFAULT_POPULATION_CAP};
+pub use roundtrip::{Section, Difference, compare, round_trip};
 pub use selection::{Coordinate, Rectangle};
-pub use scenario::{ScenHeader, ScenMessages, ScenImage};
+pub use scenario::{Scenario, ScenMessages, ScenImage, ScenCinematics};
+pub use tech::{Technology, TechnologyId};
+pub use terrain::{Terrain, TerrainInfo};
 pub use trigger::{Trigger, TriggerCondition, TriggerEffect};
-pub use unit::Unit;
+pub use unit::{Unit, UnitTypeId};
+pub use unit_info::{UnitInfo, Placement, DefaultOwner};
+pub use version::ScenVersion;
+pub use victory::VictoryConditions;
@@ -1,6 +1,6 @@
-use std::io::{Write, Error};
+use std::io::{Read, Write, Error, ErrorKind};
 use std::mem;
-use byteorder::{LittleEndian as LE, WriteBytesExt};
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
 
 use selection::Selection;
 
@@ -49,6 +49,15 @@ impl Map {
         }
     }
 
+    /// A flat map covered in a single terrain.
+    pub fn filled(size: u32, terrain: u8) -> Map {
+        let mut map = Map::new(size);
+        for _ in 0..size * size {
+            map.tiles.push(MapTile::new(terrain, 0));
+        }
+        map
+    }
+
     pub fn tile_at(&self, x: u32, y: u32) -> Option<MapTile> {
         let idx = (self.size * y + x) as usize;
         if idx < self.tiles.len() {
@@ -65,6 +74,10 @@ impl Map {
         }
     }
 
+    pub fn terrain_at(&self, x: u32, y: u32) -> Option<u8> {
+        self.tile_at(x, y).map(|tile| tile.terrain)
+    }
+
     pub fn elevation_at(&self, x: u32, y: u32) -> Option<u8> {
         match self.tile_at(x, y) {
             Some(tile) => Some(tile.elevation),
@@ -156,4 +169,31 @@ impl Map {
         }
         Ok(buf)
     }
+
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<Map, Error> {
+        let width = try!(input.read_u32::<LE>());
+        let height = try!(input.read_u32::<LE>());
+        if width != height {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("non-square maps are not supported ({}x{})", width, height)
+            ));
+        }
+
+        let mut map = Map::new(width);
+        for _ in 0..(width * height) {
+            map.tiles.push(MapTile::new(0, 0));
+        }
+        // Same order as `to_bytes`.
+        for x in 0..width {
+            for y in 0..height {
+                let terrain = try!(input.read_u8());
+                let elevation = try!(input.read_u8());
+                // ???
+                try!(input.read_u8());
+                map.put_tile(x, y, MapTile::new(terrain, elevation));
+            }
+        }
+        Ok(map)
+    }
 }
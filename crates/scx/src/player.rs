@@ -1,5 +1,11 @@
-use consts::Civilization;
-use unit::Unit;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::Path;
+
+use consts::{Civilization, UnitType};
+use tech::TechnologyId;
+use unit::{Unit, UnitTypeId};
 
 pub struct BaseResources {
     pub gold: u32,
@@ -9,22 +15,107 @@ pub struct BaseResources {
     pub ore: u32,
 }
 
+/// Starting resource amounts, as in the random map lobby.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ResourceLevel {
+    Low,
+    Medium,
+    High,
+    Deathmatch,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StartingAge {
+    Dark = 0,
+    Feudal = 1,
+    Castle = 2,
+    Imperial = 3,
+    /// Imperial Age with all technologies researched.
+    PostImperial = 4,
+}
+
+impl ResourceLevel {
+    pub fn from_name(name: &str) -> Option<ResourceLevel> {
+        match name {
+            "low" => Some(ResourceLevel::Low),
+            "medium" => Some(ResourceLevel::Medium),
+            "high" => Some(ResourceLevel::High),
+            "deathmatch" => Some(ResourceLevel::Deathmatch),
+            _ => None,
+        }
+    }
+}
+
+impl StartingAge {
+    pub fn from_name(name: &str) -> Option<StartingAge> {
+        match name {
+            "dark" => Some(StartingAge::Dark),
+            "feudal" => Some(StartingAge::Feudal),
+            "castle" => Some(StartingAge::Castle),
+            "imperial" => Some(StartingAge::Imperial),
+            "post_imperial" => Some(StartingAge::PostImperial),
+            _ => None,
+        }
+    }
+
+    pub fn from_i32(id: i32) -> Option<StartingAge> {
+        match id {
+            0 => Some(StartingAge::Dark),
+            1 => Some(StartingAge::Feudal),
+            2 => Some(StartingAge::Castle),
+            3 => Some(StartingAge::Imperial),
+            4 => Some(StartingAge::PostImperial),
+            _ => None,
+        }
+    }
+}
+
+pub const DEFAULT_POPULATION_CAP: u32 = 200;
+
 pub struct Player {
     pub name: String,
+    /// language.dll string ID to use as the name instead, or 0 to use `name`.
+    pub name_id: i32,
+    /// Name shown in the scenario editor's player data.
+    pub editor_name: String,
     pub active: u32,
     pub human: u32,
     pub civilization: Civilization,
+    pub lock_civilization: bool,
     pub resources: BaseResources,
+    pub starting_age: StartingAge,
+    pub population_cap: u32,
+    /// Tile position that the camera starts at. Defaults to the player's start position.
+    pub camera: Option<(f32, f32)>,
+    /// Index into the player colours, 0 is blue. Defaults to the colour of the player's slot.
+    pub color: Option<u32>,
+    /// Name of the custom AI, usually the .per file name without extension.
+    pub ai_name: String,
+    /// Source code of the custom AI, in the .per format.
+    pub ai_source: String,
     pub units: Vec<Unit>,
+    pub disabled_techs: Vec<TechnologyId>,
+    pub disabled_units: Vec<UnitTypeId>,
+    pub disabled_buildings: Vec<UnitTypeId>,
 }
 
 impl BaseResources {
     pub fn default() -> BaseResources {
+        BaseResources::preset(ResourceLevel::Low)
+    }
+
+    pub fn preset(level: ResourceLevel) -> BaseResources {
+        let (food, wood, gold, stone) = match level {
+            ResourceLevel::Low => (200, 200, 100, 200),
+            ResourceLevel::Medium => (500, 500, 300, 300),
+            ResourceLevel::High => (1000, 1000, 700, 700),
+            ResourceLevel::Deathmatch => (20000, 20000, 10000, 5000),
+        };
         BaseResources {
-            gold: 100,
-            wood: 200,
-            food: 200,
-            stone: 200,
+            gold: gold,
+            wood: wood,
+            food: food,
+            stone: stone,
             ore: 0,
         }
     }
@@ -34,9 +125,12 @@ impl Player {
     pub fn empty() -> Player {
         Player {
             name: String::from(""),
+            name_id: 0,
+            editor_name: String::new(),
             active: 0,
             human: 0,
             civilization: Civilization::None,
+            lock_civilization: false,
             resources: BaseResources {
                 wood: 0,
                 food: 0,
@@ -44,18 +138,44 @@ impl Player {
                 stone: 0,
                 ore: 0,
             },
+            starting_age: StartingAge::Dark,
+            population_cap: DEFAULT_POPULATION_CAP,
+            camera: None,
+            color: None,
+            ai_name: String::new(),
+            ai_source: String::new(),
             units: vec![],
+            disabled_techs: vec![],
+            disabled_units: vec![],
+            disabled_buildings: vec![],
         }
     }
 
+    /// The player's start position: their first Town Center, or else their first unit.
+    pub fn start_position(&self) -> Option<(f32, f32)> {
+        self.units.iter()
+            .find(|unit| unit.unit_type == UnitTypeId::Known(UnitType::TownCenter))
+            .or_else(|| self.units.first())
+            .map(|unit| (unit.x, unit.y))
+    }
+
+    /// Use a .per file as the player's AI. The AI is named after the file.
+    pub fn with_ai_file<P: AsRef<Path>>(self, path: P) -> Result<Player, io::Error> {
+        let path = path.as_ref();
+        let mut source = String::new();
+        try!(try!(File::open(path)).read_to_string(&mut source));
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        Ok(Player {
+            ai_name: name,
+            ai_source: source,
+            ..self
+        })
+    }
+
     pub fn with_units(self, units: Vec<Unit>) -> Player {
         Player {
-            name: self.name,
-            active: self.active,
-            human: self.human,
-            civilization: self.civilization,
-            resources: self.resources,
             units: units,
+            ..self
         }
     }
 }
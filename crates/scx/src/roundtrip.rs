@@ -0,0 +1,165 @@
+use std::io;
+use std::io::prelude::*;
+use byteorder::{ByteOrder, LittleEndian as LE};
+use flate2::{Decompress, Flush, Status};
+
+use scenario::Scenario;
+
+/// The sections of a scenario file, in the order in which they appear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Section {
+    /// The uncompressed header.
+    Header,
+    PlayerNames,
+    PlayerSettings,
+    Messages,
+    Cinematics,
+    Image,
+    AiScripts,
+    Resources,
+    Goals,
+    Diplomacy,
+    Disables,
+    View,
+    Map,
+    Units,
+    PlayerDetails,
+    Triggers,
+    IncludedFiles,
+}
+
+/// The first byte that differs between two scenario files.
+///
+/// `offset` is relative to the start of the uncompressed header for `Section::Header`, and
+/// relative to the start of the inflated body for all other sections.
+#[derive(Clone, Copy, Debug)]
+pub struct Difference {
+    pub section: Section,
+    pub offset: usize,
+    pub expected: Option<u8>,
+    pub actual: Option<u8>,
+}
+
+/// Wraps a reader and remembers where each section of the scenario starts.
+pub struct SectionReader<R: Read> {
+    inner: R,
+    position: u64,
+    sections: Vec<(Section, u64)>,
+}
+
+impl<R: Read> SectionReader<R> {
+    pub fn new(inner: R) -> SectionReader<R> {
+        SectionReader {
+            inner: inner,
+            position: 0,
+            sections: vec![],
+        }
+    }
+
+    pub fn begin(&mut self, section: Section) {
+        self.sections.push((section, self.position));
+    }
+
+    pub fn into_sections(self) -> Vec<(Section, u64)> {
+        self.sections
+    }
+}
+
+impl<R: Read> Read for SectionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = try!(self.inner.read(buf));
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+fn split_header(bytes: &[u8]) -> Result<(&[u8], Vec<u8>), io::Error> {
+    if bytes.len() < 8 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "file too short"));
+    }
+    let header_length = LE::read_i32(&bytes[4..8]);
+    if header_length < 0 || header_length as usize > bytes.len() - 8 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid header length"));
+    }
+    let header_end = 8 + header_length as usize;
+    Ok((&bytes[..header_end], try!(inflate(&bytes[header_end..]))))
+}
+
+// Not using `DeflateDecoder` here, because it rejects deflate streams that were never
+// finished, and older versions of `Scenario::to_bytes` only did a sync flush.
+fn inflate(compressed: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let mut decompressor = Decompress::new(false);
+    let mut body = Vec::with_capacity(compressed.len() * 4);
+    loop {
+        if body.len() == body.capacity() {
+            body.reserve(compressed.len() * 4);
+        }
+        let consumed = decompressor.total_in() as usize;
+        let produced = decompressor.total_out();
+        let status = try!(
+            decompressor.decompress_vec(&compressed[consumed..], &mut body, Flush::None)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "corrupt deflate stream"))
+        );
+        let done = decompressor.total_in() as usize == compressed.len() &&
+            decompressor.total_out() == produced;
+        if status == Status::StreamEnd || done {
+            return Ok(body);
+        }
+    }
+}
+
+fn first_difference(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    match expected.iter().zip(actual).position(|(a, b)| a != b) {
+        Some(offset) => Some(offset),
+        None if expected.len() != actual.len() => Some(expected.len().min(actual.len())),
+        None => None,
+    }
+}
+
+fn section_at(sections: &[(Section, u64)], offset: usize) -> Section {
+    let mut section = Section::PlayerNames;
+    for &(s, start) in sections {
+        if start as usize > offset {
+            break;
+        }
+        section = s;
+    }
+    section
+}
+
+/// Compare two scenario files byte-by-byte, looking at the inflated body rather than the
+/// compressed data.
+pub fn compare(expected: &[u8], actual: &[u8]) -> Result<Option<Difference>, io::Error> {
+    let (expected_header, expected_body) = try!(split_header(expected));
+    let (actual_header, actual_body) = try!(split_header(actual));
+
+    if let Some(offset) = first_difference(expected_header, actual_header) {
+        return Ok(Some(Difference {
+            section: Section::Header,
+            offset: offset,
+            expected: expected_header.get(offset).cloned(),
+            actual: actual_header.get(offset).cloned(),
+        }));
+    }
+
+    match first_difference(&expected_body, &actual_body) {
+        Some(offset) => {
+            let (_, sections) = try!(Scenario::read_sections(expected));
+            Ok(Some(Difference {
+                section: section_at(&sections, offset),
+                offset: offset,
+                expected: expected_body.get(offset).cloned(),
+                actual: actual_body.get(offset).cloned(),
+            }))
+        },
+        None => Ok(None),
+    }
+}
+
+/// Read a scenario file, write it back out using `Scenario::to_bytes`, and return the first
+/// difference between the two, if any.
+pub fn round_trip(bytes: &[u8]) -> Result<Option<Difference>, io::Error> {
+    let scenario = try!(Scenario::from_bytes(bytes));
+    let written = try!(scenario.to_bytes());
+    compare(bytes, &written)
+}
@@ -1,64 +1,582 @@
 use std::io;
 use std::io::prelude::*;
 use std::mem;
-use byteorder::{LittleEndian as LE, WriteBytesExt};
-use flate2::{Compression, Flush, Compress, Status};
+use std::time::{SystemTime, UNIX_EPOCH};
+use byteorder::{ByteOrder, LittleEndian as LE, ReadBytesExt, WriteBytesExt};
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
 
+use bitmap::Bitmap;
+use consts::{Civilization, MapSize};
+use diplomacy::{Diplomacy, DiplomacyStance};
 use map::Map;
-use player::Player;
+use player::{Player, StartingAge};
+use roundtrip::{Section, SectionReader};
+use tech::TechnologyId;
+use terrain::Terrain;
+use trigger::{Trigger, read_display_order, write_display_order};
+use unit::{Unit, UnitTypeId};
+use version::ScenVersion;
+use victory::VictoryConditions;
+use util::{read_bytes, read_fixed_str, read_str16, read_str32, write_str16, write_str32, skip, expect_u32};
 
 const HEADER_SEPARATOR: u32 = 0xFFFFFF9D;
 
-pub struct ScenHeader<'a> {
-    pub version: &'a[u8; 4],
+const MAX_DISABLED_UNITS: usize = 30;
+const MAX_DISABLED_BUILDINGS: usize = 20;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn disabled_id(id: i32) -> Result<u16, io::Error> {
+    if id < 0 || id > u16::max_value() as i32 {
+        return Err(invalid_data(format!("invalid disabled ID {}", id)));
+    }
+    Ok(id as u16)
+}
+
+// A fixed size block of disabled IDs: a count for each player, then `max` IDs for each
+// player, padded with -1.
+fn read_disables<R: Read>(input: &mut R, max: usize) -> Result<Vec<Vec<u16>>, io::Error> {
+    let mut counts = vec![];
+    for _ in 0..16 {
+        counts.push(try!(input.read_i32::<LE>()));
+    }
+    let mut lists = vec![];
+    for count in counts {
+        if count > max as i32 {
+            return Err(invalid_data(format!("too many disabled IDs: {}", count)));
+        }
+        let mut ids = vec![];
+        for i in 0..max {
+            let id = try!(input.read_i32::<LE>());
+            if (i as i32) < count {
+                ids.push(try!(disabled_id(id)));
+            }
+        }
+        lists.push(ids);
+    }
+    Ok(lists)
+}
+
+fn write_disables<W: Write>(output: &mut W, lists: &[Vec<u16>], max: usize, what: &str) -> Result<(), io::Error> {
+    if let Some(ids) = lists.iter().find(|ids| ids.len() > max) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot disable {} {}, the maximum is {}", ids.len(), what, max)
+        ));
+    }
+    for i in 0..16 {
+        try!(output.write_i32::<LE>(match lists.get(i) {
+            Some(ids) => ids.len() as i32,
+            // Unused players
+            None if i < 8 => 0,
+            None => -1,
+        }));
+    }
+    for i in 0..16 {
+        let ids = lists.get(i).map(|ids| &ids[..]).unwrap_or(&[]);
+        for j in 0..max {
+            try!(output.write_i32::<LE>(ids.get(j).map(|&id| id as i32).unwrap_or(-1)));
+        }
+    }
+    Ok(())
+}
+
+/// A scenario file. Start from `Scenario::new` and fill it in with the `with_*` methods, or
+/// read an existing file with `Scenario::from_bytes`.
+pub struct Scenario {
+    pub version: ScenVersion,
     pub header_type: i32,
+    /// Last save time, in seconds since the Unix epoch.
     pub timestamp: i32,
-    pub instructions: &'a str,
+    /// Shown in the scenario list. Usually the same as `messages.objectives`.
+    pub instructions: String,
     pub players: Vec<Player>,
-    pub filename: &'a str,
-    pub messages: ScenMessages<'a>,
-    pub image: ScenImage<'a>,
+    pub diplomacy: Diplomacy,
+    pub victory: VictoryConditions,
+    pub filename: String,
+    pub messages: ScenMessages,
+    pub cinematics: ScenCinematics,
+    pub image: ScenImage,
     pub map: Map,
+    pub gaia_units: Vec<Unit>,
+    pub triggers: Vec<Trigger>,
 }
 
-pub struct ScenMessages<'a> {
-    pub objectives: &'a str,
-    pub hints: &'a str,
-    pub scouts: &'a str,
-    pub history: &'a str,
-    pub victory: &'a str,
-    pub loss: &'a str,
+pub struct ScenMessages {
+    pub objectives: String,
+    pub hints: String,
+    pub scouts: String,
+    pub history: String,
+    pub victory: String,
+    pub loss: String,
+    /// language.dll string IDs that the game shows instead of the texts above, in the player's
+    /// language. 0 shows the text.
+    pub objectives_id: i32,
+    pub hints_id: i32,
+    pub scouts_id: i32,
+    pub history_id: i32,
+    pub victory_id: i32,
+    pub loss_id: i32,
 }
 
-pub struct ScenImage<'a> {
-    pub filename: &'a str,
-    pub included: bool,
-    pub width: i32,
-    pub height: i32,
+pub struct ScenImage {
+    pub filename: String,
+    /// Image shown on the instructions screen, stored in the scenario.
+    pub bitmap: Option<Bitmap>,
     pub include: i16,
 }
 
-impl<'a> ScenHeader<'a> {
+/// Video files that play before the scenario starts, and when it is won or lost.
+pub struct ScenCinematics {
+    pub pregame: String,
+    pub victory: String,
+    pub loss: String,
+}
+
+impl Scenario {
+    /// An empty scenario: eight inactive players, a tiny grass map and no messages or triggers.
+    pub fn new(version: ScenVersion) -> Scenario {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs() as i32)
+            .unwrap_or(0);
+        Scenario {
+            version: version,
+            header_type: 2,
+            timestamp: timestamp,
+            instructions: String::new(),
+            players: (0..8).map(|_| Player::empty()).collect(),
+            diplomacy: Diplomacy::new(),
+            victory: VictoryConditions::Standard,
+            filename: String::new(),
+            messages: ScenMessages::new(),
+            cinematics: ScenCinematics::new(),
+            image: ScenImage::new(),
+            map: Map::filled(MapSize::Tiny as u32, Terrain::Grass1 as u8),
+            gaia_units: vec![],
+            triggers: vec![],
+        }
+    }
+
+    pub fn with_timestamp(self, timestamp: i32) -> Scenario {
+        Scenario { timestamp: timestamp, ..self }
+    }
+
+    pub fn with_filename(self, filename: &str) -> Scenario {
+        Scenario { filename: String::from(filename), ..self }
+    }
+
+    pub fn with_players(self, players: Vec<Player>) -> Scenario {
+        Scenario { players: players, ..self }
+    }
+
+    pub fn with_diplomacy(self, diplomacy: Diplomacy) -> Scenario {
+        Scenario { diplomacy: diplomacy, ..self }
+    }
+
+    pub fn with_victory(self, victory: VictoryConditions) -> Scenario {
+        Scenario { victory: victory, ..self }
+    }
+
+    /// Set the messages. The instructions also become the description in the scenario list.
+    pub fn with_messages(self, messages: ScenMessages) -> Scenario {
+        Scenario {
+            instructions: messages.objectives.clone(),
+            messages: messages,
+            ..self
+        }
+    }
+
+    pub fn with_cinematics(self, cinematics: ScenCinematics) -> Scenario {
+        Scenario { cinematics: cinematics, ..self }
+    }
+
+    pub fn with_image(self, image: ScenImage) -> Scenario {
+        Scenario { image: image, ..self }
+    }
+
+    pub fn with_map(self, map: Map) -> Scenario {
+        Scenario { map: map, ..self }
+    }
+
+    pub fn with_gaia_units(self, units: Vec<Unit>) -> Scenario {
+        Scenario { gaia_units: units, ..self }
+    }
+
+    pub fn with_triggers(self, triggers: Vec<Trigger>) -> Scenario {
+        Scenario { triggers: triggers, ..self }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Scenario, io::Error> {
+        Scenario::from_reader(bytes)
+    }
+
+    pub fn from_reader<R: Read>(input: R) -> Result<Scenario, io::Error> {
+        Scenario::read_sections(input).map(|(scenario, _)| scenario)
+    }
+
+    // Read a scenario, keeping track of where each section of the inflated body starts.
+    pub fn read_sections<R: Read>(mut input: R) -> Result<(Scenario, Vec<(Section, u64)>), io::Error> {
+        let mut version_string = [0; 4];
+        try!(input.read_exact(&mut version_string));
+        let mut version = match ScenVersion::from_header(&version_string) {
+            Some(version) => version,
+            None => return Err(invalid_data(
+                format!("unsupported version {}", String::from_utf8_lossy(&version_string))
+            )),
+        };
+        // Header length
+        try!(input.read_i32::<LE>());
+        let header_type = try!(input.read_i32::<LE>());
+        let timestamp = try!(input.read_i32::<LE>());
+        let instructions = try!(read_str32(&mut input));
+        try!(input.read_i32::<LE>());
+        let num_players = try!(input.read_i32::<LE>());
+        if num_players < 0 || num_players > 16 {
+            return Err(invalid_data(format!("invalid player count {}", num_players)));
+        }
+
+        let mut players = vec![];
+        for _ in 0..num_players {
+            players.push(Player::empty());
+        }
+
+        let mut body = SectionReader::new(DeflateDecoder::new(input));
+        let zlib_buf = &mut body;
+
+        zlib_buf.begin(Section::PlayerNames);
+        // Next unit ID
+        try!(zlib_buf.read_u32::<LE>());
+        let data_version = try!(zlib_buf.read_f32::<LE>());
+        version = version.with_data_version(data_version);
+
+        for i in 0..16 {
+            let name = try!(read_fixed_str(zlib_buf, 256));
+            if players.len() > i {
+                players[i].name = name;
+            }
+        }
+
+        if version.has_player_name_ids() {
+            for i in 0..16 {
+                let name_id = try!(zlib_buf.read_i32::<LE>());
+                if players.len() > i {
+                    players[i].name_id = name_id;
+                }
+            }
+        }
+
+        zlib_buf.begin(Section::PlayerSettings);
+        for i in 0..16 {
+            let active = try!(zlib_buf.read_u32::<LE>());
+            let human = try!(zlib_buf.read_u32::<LE>());
+            let civilization = try!(zlib_buf.read_u32::<LE>());
+            try!(zlib_buf.read_u32::<LE>());
+            if players.len() > i {
+                let ref mut p = players[i];
+                p.active = active;
+                p.human = human;
+                if civilization > 255 {
+                    return Err(invalid_data(format!("unknown civilization {}", civilization)));
+                }
+                p.civilization = try!(Civilization::from_u8(civilization as u8));
+            }
+        }
+
+        if version.has_civilization_locks() {
+            for i in 0..16 {
+                let locked = try!(zlib_buf.read_u32::<LE>()) != 0;
+                if players.len() > i {
+                    players[i].lock_civilization = locked;
+                }
+            }
+        }
+
+        zlib_buf.begin(Section::Messages);
+        try!(zlib_buf.read_u32::<LE>());
+        try!(zlib_buf.read_u8());
+        try!(zlib_buf.read_f32::<LE>());
+        let filename = try!(read_str16(zlib_buf));
+
+        let messages = try!(ScenMessages::from_reader(zlib_buf, version));
+
+        zlib_buf.begin(Section::Cinematics);
+        let cinematics = ScenCinematics {
+            pregame: try!(read_str16(zlib_buf)),
+            victory: try!(read_str16(zlib_buf)),
+            loss: try!(read_str16(zlib_buf)),
+        };
+
+        zlib_buf.begin(Section::Image);
+        let image = try!(ScenImage::from_reader(zlib_buf));
+
+        zlib_buf.begin(Section::AiScripts);
+        for _ in 0..(16 * 2) {
+            try!(read_str16(zlib_buf));
+        }
+
+        for i in 0..16 {
+            let ai_name = try!(read_str16(zlib_buf));
+            if players.len() > i {
+                players[i].ai_name = ai_name;
+            }
+        }
+        for i in 0..16 {
+            try!(zlib_buf.read_i32::<LE>());
+            try!(zlib_buf.read_i32::<LE>());
+            let ai_source = try!(read_str32(zlib_buf));
+            if players.len() > i {
+                players[i].ai_source = ai_source;
+            }
+        }
+        if version.has_ai_types() {
+            // AI type
+            try!(skip(zlib_buf, 16));
+        }
+
+        try!(expect_u32(zlib_buf, HEADER_SEPARATOR, "separator"));
+
+        zlib_buf.begin(Section::Resources);
+        // Resources
+        for i in 0..16 {
+            let gold = try!(zlib_buf.read_u32::<LE>());
+            let wood = try!(zlib_buf.read_u32::<LE>());
+            let food = try!(zlib_buf.read_u32::<LE>());
+            let stone = try!(zlib_buf.read_u32::<LE>());
+            let ore = try!(zlib_buf.read_u32::<LE>());
+            try!(zlib_buf.read_u32::<LE>());
+            if players.len() > i {
+                let ref mut r = players[i].resources;
+                r.gold = gold;
+                r.wood = wood;
+                r.food = food;
+                r.stone = stone;
+                r.ore = ore;
+            }
+        }
+
+        try!(expect_u32(zlib_buf, HEADER_SEPARATOR, "separator"));
+
+        zlib_buf.begin(Section::Goals);
+        let victory = try!(VictoryConditions::from_reader(zlib_buf));
+
+        zlib_buf.begin(Section::Diplomacy);
+        let mut diplomacy = Diplomacy::new();
+        for player in 1..17 {
+            for other in 1..17 {
+                let stance = try!(zlib_buf.read_i32::<LE>());
+                let stance = match DiplomacyStance::from_i32(stance) {
+                    Some(stance) => stance,
+                    None => return Err(invalid_data(format!("unknown diplomacy stance {}", stance))),
+                };
+                try!(diplomacy.set_stance(player, other, stance));
+            }
+        }
+        // ???
+        try!(skip(zlib_buf, 11520));
+
+        try!(expect_u32(zlib_buf, HEADER_SEPARATOR, "separator"));
+
+        for i in 0..16 {
+            diplomacy.allied_victory[i] = try!(zlib_buf.read_i32::<LE>()) != 0;
+        }
+
+        zlib_buf.begin(Section::Disables);
+        // Disabled technologies, units, buildings
+        let (techs, units, buildings) = if version.has_disabled_units() {
+            (try!(read_disables(zlib_buf, version.max_disabled_techs())),
+             try!(read_disables(zlib_buf, MAX_DISABLED_UNITS)),
+             try!(read_disables(zlib_buf, MAX_DISABLED_BUILDINGS)))
+        } else {
+            (try!(read_disables(zlib_buf, version.max_disabled_techs())), vec![], vec![])
+        };
+        for (i, player) in players.iter_mut().enumerate() {
+            if let Some(ids) = techs.get(i) {
+                player.disabled_techs = ids.iter().map(|&id| TechnologyId::from_u16(id)).collect();
+            }
+            if let Some(ids) = units.get(i) {
+                player.disabled_units = ids.iter().map(|&id| UnitTypeId::from_u16(id)).collect();
+            }
+            if let Some(ids) = buildings.get(i) {
+                player.disabled_buildings = ids.iter().map(|&id| UnitTypeId::from_u16(id)).collect();
+            }
+        }
+        // ???, ???, All Techs
+        try!(skip(zlib_buf, 3 * mem::size_of::<u32>()));
+        // Starting age
+        for i in 0..16 {
+            let age = try!(zlib_buf.read_i32::<LE>());
+            if i < 8 && players.len() > i {
+                players[i].starting_age = match StartingAge::from_i32(age) {
+                    Some(age) => age,
+                    None => return Err(invalid_data(format!("unknown starting age {}", age))),
+                };
+            }
+        }
+
+        try!(expect_u32(zlib_buf, HEADER_SEPARATOR, "separator"));
+
+        zlib_buf.begin(Section::View);
+        // Camera
+        try!(zlib_buf.read_i32::<LE>());
+        try!(zlib_buf.read_i32::<LE>());
+        if version.has_view_ai_type() {
+            // AI type
+            try!(zlib_buf.read_u32::<LE>());
+        }
+
+        zlib_buf.begin(Section::Map);
+        let map = try!(Map::from_reader(zlib_buf));
+
+        zlib_buf.begin(Section::Units);
+        let num_unit_sections = try!(zlib_buf.read_u32::<LE>());
+        if num_unit_sections < 1 || num_unit_sections > 17 {
+            return Err(invalid_data(format!("invalid unit section count {}", num_unit_sections)));
+        }
+
+        // Resources again
+        for i in 0..(num_unit_sections as usize - 1) {
+            try!(skip(zlib_buf, 6 * mem::size_of::<f32>()));
+            if version.has_population() {
+                let population_cap = try!(zlib_buf.read_f32::<LE>());
+                if players.len() > i {
+                    players[i].population_cap = population_cap as u32;
+                }
+            }
+        }
+
+        let mut gaia_units = vec![];
+        for i in 0..(num_unit_sections as usize) {
+            let num_units = try!(zlib_buf.read_u32::<LE>());
+            let mut units = vec![];
+            for _ in 0..num_units {
+                units.push(try!(Unit::from_reader(zlib_buf)));
+            }
+            if i == 0 {
+                gaia_units = units;
+            } else if players.len() >= i {
+                players[i - 1].units = units;
+            }
+            // Units belonging to players that are not in the scenario are dropped.
+        }
+
+        zlib_buf.begin(Section::PlayerDetails);
+        // Playable players
+        let num_players_again = try!(zlib_buf.read_u32::<LE>());
+        for i in 0..(num_players_again as usize).saturating_sub(1) {
+            let editor_name = try!(read_str16(zlib_buf));
+            let camera_x = try!(zlib_buf.read_f32::<LE>());
+            let camera_y = try!(zlib_buf.read_f32::<LE>());
+            // Initial camera x, y, allied victory
+            try!(skip(zlib_buf, 2 + 2 + 1));
+            let diplomacy_len = try!(zlib_buf.read_u16::<LE>()) as usize;
+            try!(skip(zlib_buf, diplomacy_len));
+            try!(skip(zlib_buf, 9 * mem::size_of::<i32>()));
+            let color = try!(zlib_buf.read_u32::<LE>());
+            if players.len() > i {
+                let ref mut p = players[i];
+                p.editor_name = editor_name;
+                p.camera = Some((camera_x, camera_y));
+                p.color = Some(color);
+            }
+            let victory_version = try!(zlib_buf.read_f32::<LE>());
+            let unknown_count = try!(zlib_buf.read_u16::<LE>()) as usize;
+            if victory_version == 2.0 {
+                try!(skip(zlib_buf, 8));
+            }
+            try!(skip(zlib_buf, unknown_count * 44));
+            try!(skip(zlib_buf, 7));
+            try!(zlib_buf.read_i32::<LE>());
+        }
+
+        zlib_buf.begin(Section::Triggers);
+        // Trigger version
+        try!(zlib_buf.read_f64::<LE>());
+        try!(zlib_buf.read_u8());
+        let num_triggers = try!(zlib_buf.read_i32::<LE>());
+        let mut triggers = vec![];
+        for _ in 0..num_triggers {
+            triggers.push(try!(Trigger::from_reader(zlib_buf)));
+        }
+        let positions = try!(read_display_order(zlib_buf, triggers.len()));
+        for (trigger, position) in triggers.iter_mut().zip(positions) {
+            trigger.display_order = position;
+        }
+
+        zlib_buf.begin(Section::IncludedFiles);
+        try!(zlib_buf.read_u32::<LE>());
+        try!(zlib_buf.read_u32::<LE>());
+
+        let scenario = Scenario {
+            version: version,
+            header_type: header_type,
+            timestamp: timestamp,
+            instructions: instructions,
+            players: players,
+            diplomacy: diplomacy,
+            victory: victory,
+            filename: filename,
+            messages: messages,
+            cinematics: cinematics,
+            image: image,
+            map: map,
+            gaia_units: gaia_units,
+            triggers: triggers,
+        };
+        Ok((scenario, body.into_sections()))
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, io::Error> {
         let mut buf = vec![];
+        try!(self.write_to(&mut buf, Compression::Default));
+        Ok(buf)
+    }
 
+    /// Write the scenario file. The body is deflated as it is written, at the given level.
+    pub fn write_to<W: Write>(&self, output: &mut W, compression: Compression) -> Result<(), io::Error> {
         let instructions_length = self.instructions.len() as i32;
-        let header_length = 20 + instructions_length;
-        try!(buf.write(self.version));
-        try!(buf.write_i32::<LE>(header_length));
-        try!(buf.write_i32::<LE>(self.header_type));
-        try!(buf.write_i32::<LE>(self.timestamp));
-        try!(buf.write_i32::<LE>(instructions_length));
-        try!(buf.write(self.instructions.as_bytes()));
-        try!(buf.write_i32::<LE>(0));
-        try!(buf.write_i32::<LE>(self.players.len() as i32));
-
-        let mut zlib_buf = vec![];
+        let version = self.version;
+        if self.map.size > version.max_map_size() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("map size {} is too large for {:?}", self.map.size, version)
+            ));
+        }
+        if self.diplomacy.lock_teams {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "this version cannot lock teams"
+            ));
+        }
+
+        let mut header_buf = vec![];
+        try!(header_buf.write_i32::<LE>(self.header_type));
+        try!(header_buf.write_i32::<LE>(self.timestamp));
+        try!(header_buf.write_i32::<LE>(instructions_length));
+        try!(header_buf.write_all(self.instructions.as_bytes()));
+        try!(header_buf.write_i32::<LE>(0));
+        try!(header_buf.write_i32::<LE>(self.players.len() as i32));
+
+        try!(output.write_all(version.header_version()));
+        try!(output.write_i32::<LE>(header_buf.len() as i32));
+        try!(output.write_all(&header_buf));
+
+        let mut zlib_buf = DeflateEncoder::new(output, compression);
         try!(zlib_buf.write_u32::<LE>(19246));
-        try!(zlib_buf.write_f32::<LE>(1.22 /* UserPatch */));
+        try!(zlib_buf.write_f32::<LE>(version.data_version()));
         for i in 0..16 {
             if self.players.len() > i {
                 let name = &self.players[i].name;
+                // Leave room for the NUL terminator.
+                if name.len() >= 256 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("player name is {} bytes long, the maximum is 255", name.len())
+                    ));
+                }
                 try!(zlib_buf.write_all(name.as_bytes()));
                 try!(zlib_buf.write_all(&vec![0; 256 - name.len()]));
             } else {
@@ -66,12 +584,13 @@ impl<'a> ScenHeader<'a> {
             }
         }
 
-        for i in 0..16 {
-            if self.players.len() > i {
-                // player name ID in string table
-                try!(zlib_buf.write_i32::<LE>(0));
-            } else {
-                try!(zlib_buf.write_i32::<LE>(0));
+        if version.has_player_name_ids() {
+            for i in 0..16 {
+                if self.players.len() > i {
+                    try!(zlib_buf.write_i32::<LE>(self.players[i].name_id));
+                } else {
+                    try!(zlib_buf.write_i32::<LE>(0));
+                }
             }
         }
 
@@ -90,20 +609,25 @@ impl<'a> ScenHeader<'a> {
             try!(zlib_buf.write_u32::<LE>(4));
         }
 
+        if version.has_civilization_locks() {
+            for i in 0..16 {
+                let locked = self.players.len() > i && self.players[i].lock_civilization;
+                try!(zlib_buf.write_u32::<LE>(if locked { 1 } else { 0 }));
+            }
+        }
+
         try!(zlib_buf.write_u32::<LE>(1));
         try!(zlib_buf.write_all(&[0]));
         try!(zlib_buf.write_f32::<LE>(-1.0));
-        try!(zlib_buf.write_u16::<LE>(self.filename.len() as u16));
-        try!(zlib_buf.write_all(self.filename.as_bytes()));
+        try!(write_str16(&mut zlib_buf, &self.filename));
 
         try!(zlib_buf.write_all(
-            &try!(self.messages.to_bytes())
+            &try!(self.messages.to_bytes(version))
         ));
 
-        // cinematics
-        try!(zlib_buf.write_u16::<LE>(0));
-        try!(zlib_buf.write_u16::<LE>(0));
-        try!(zlib_buf.write_u16::<LE>(0));
+        try!(write_str16(&mut zlib_buf, &self.cinematics.pregame));
+        try!(write_str16(&mut zlib_buf, &self.cinematics.victory));
+        try!(write_str16(&mut zlib_buf, &self.cinematics.loss));
 
         try!(zlib_buf.write_all(
             &try!(self.image.to_bytes())
@@ -111,38 +635,34 @@ impl<'a> ScenHeader<'a> {
 
         for _ in 0..16 {
             // two 0-length strings
-            try!(zlib_buf.write_u16::<LE>(0));
-            try!(zlib_buf.write_u16::<LE>(0));
+            try!(write_str16(&mut zlib_buf, ""));
+            try!(write_str16(&mut zlib_buf, ""));
         }
 
         // Player AI names
-        for _ in 0..8 {
-            try!(zlib_buf.write_u16::<LE>(0));
-        }
-        // Unused players
-        for _ in 0..8 {
-            let name = "RandomGame";
-            try!(zlib_buf.write_u16::<LE>(name.len() as u16));
-            try!(zlib_buf.write(&name.as_bytes()));
+        for i in 0..16 {
+            let ai_name = match self.players.get(i) {
+                Some(p) => &p.ai_name[..],
+                None if i < 8 => "",
+                // Unused players
+                None => "RandomGame",
+            };
+            try!(write_str16(&mut zlib_buf, ai_name));
         }
         // AI source code
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(0));
-            try!(zlib_buf.write_i32::<LE>(0));
-            // 0-length AI source code string
+        for i in 0..16 {
             try!(zlib_buf.write_i32::<LE>(0));
-        }
-        // Source code for unused players
-        for _ in 0..(3 * 8) {
             try!(zlib_buf.write_i32::<LE>(0));
+            try!(write_str32(&mut zlib_buf, self.players.get(i).map(|p| &p.ai_source[..]).unwrap_or("")));
         }
 
-        // AI type
-        for _ in 0..8 {
-            try!(zlib_buf.write(&[0]));
+        if version.has_ai_types() {
+            // AI type, 1 for players with a custom AI
+            for i in 0..16 {
+                let custom = self.players.get(i).map(|p| !p.ai_source.is_empty()).unwrap_or(false);
+                try!(zlib_buf.write_u8(if custom { 1 } else { 0 }));
+            }
         }
-        // Unused players
-        try!(zlib_buf.write(&[0; 8]));
 
         try!(zlib_buf.write_u32::<LE>(HEADER_SEPARATOR));
 
@@ -150,78 +670,58 @@ impl<'a> ScenHeader<'a> {
         for i in 0..16 {
             if self.players.len() > i {
                 let p = &self.players[i];
-                try!(zlib_buf.write_u32::<LE>(p.resources.food));
-                try!(zlib_buf.write_u32::<LE>(p.resources.wood));
                 try!(zlib_buf.write_u32::<LE>(p.resources.gold));
+                try!(zlib_buf.write_u32::<LE>(p.resources.wood));
+                try!(zlib_buf.write_u32::<LE>(p.resources.food));
                 try!(zlib_buf.write_u32::<LE>(p.resources.stone));
                 try!(zlib_buf.write_u32::<LE>(p.resources.ore));
                 try!(zlib_buf.write_u32::<LE>(0 /* ??? */));
             }
             else {
                 // Unused players
-                try!(zlib_buf.write(&vec![0; 6 * mem::size_of::<u32>()]));
+                try!(zlib_buf.write_all(&vec![0; 6 * mem::size_of::<u32>()]));
             }
         }
 
         try!(zlib_buf.write_u32::<LE>(HEADER_SEPARATOR));
 
-        // Scenario goals: 10 * int32
-        // Conquest; unknown; Relics; unknown; Exploration; unknown;
-        // All; Mode; Score; Time Limit
-        let scenario_goals_size = 10 * mem::size_of::<i32>();
-        try!(zlib_buf.write(&vec![0; scenario_goals_size]));
+        try!(self.victory.write_to(&mut zlib_buf));
 
         // Diplomacy
-        for _ in 0..16 {
-            for _ in 0..16 {
-                try!(zlib_buf.write_i32::<LE>(0));
+        for player in 1..17 {
+            for other in 1..17 {
+                try!(zlib_buf.write_i32::<LE>(self.diplomacy.stance(player, other) as i32));
             }
         }
 
         // ???
-        try!(zlib_buf.write(&[0; 11520]));
+        try!(zlib_buf.write_all(&[0; 11520]));
 
         try!(zlib_buf.write_u32::<LE>(HEADER_SEPARATOR));
 
         // Allied victory
-        for _ in 0..16 {
-            try!(zlib_buf.write_i32::<LE>(0));
-        }
-
-        // Technology count??
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(0));
-        }
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(-1));
-        }
-        // Technology something??
-        for _ in 0..(16 * 30) {
-            try!(zlib_buf.write_i32::<LE>(-1));
-        }
-
-        // Unit count??
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(0));
-        }
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(-1));
-        }
-        // Unit something??
-        for _ in 0..(16 * 30) {
-            try!(zlib_buf.write_i32::<LE>(-1));
+        for &allied_victory in &self.diplomacy.allied_victory {
+            try!(zlib_buf.write_i32::<LE>(if allied_victory { 1 } else { 0 }));
         }
 
-        // Building count??
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(0));
-        }
-        for _ in 0..8 {
-            try!(zlib_buf.write_i32::<LE>(-1));
-        }
-        // Buildings something??
-        for _ in 0..(16 * 20) {
-            try!(zlib_buf.write_i32::<LE>(-1));
+        let techs: Vec<Vec<u16>> = self.players.iter()
+            .map(|p| p.disabled_techs.iter().map(TechnologyId::id).collect())
+            .collect();
+        let units: Vec<Vec<u16>> = self.players.iter()
+            .map(|p| p.disabled_units.iter().map(UnitTypeId::id).collect())
+            .collect();
+        let buildings: Vec<Vec<u16>> = self.players.iter()
+            .map(|p| p.disabled_buildings.iter().map(UnitTypeId::id).collect())
+            .collect();
+        try!(write_disables(&mut zlib_buf, &techs, version.max_disabled_techs(), "technologies"));
+        if version.has_disabled_units() {
+            try!(write_disables(&mut zlib_buf, &units, MAX_DISABLED_UNITS, "units"));
+            try!(write_disables(&mut zlib_buf, &buildings, MAX_DISABLED_BUILDINGS, "buildings"));
+        } else if units.iter().chain(buildings.iter()).any(|ids| !ids.is_empty()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "this version cannot disable units or buildings"
+            ));
         }
 
         // ???
@@ -231,8 +731,12 @@ impl<'a> ScenHeader<'a> {
         try!(zlib_buf.write_u32::<LE>(0));
 
         // Starting age
-        for _ in 0..8 {
-            try!(zlib_buf.write_u32::<LE>(0));
+        for i in 0..8 {
+            if self.players.len() > i {
+                try!(zlib_buf.write_i32::<LE>(self.players[i].starting_age as i32));
+            } else {
+                try!(zlib_buf.write_u32::<LE>(0));
+            }
         }
         // Gaia
         try!(zlib_buf.write_u32::<LE>(0));
@@ -247,11 +751,13 @@ impl<'a> ScenHeader<'a> {
         try!(zlib_buf.write_i32::<LE>(0 /* x */));
         try!(zlib_buf.write_i32::<LE>(0 /* y */));
 
-        // AI type
-        try!(zlib_buf.write_u32::<LE>(0));
+        if version.has_view_ai_type() {
+            // AI type
+            try!(zlib_buf.write_u32::<LE>(0));
+        }
 
         // Map tiles
-        try!(zlib_buf.write(
+        try!(zlib_buf.write_all(
             &try!(self.map.to_bytes())
         ));
 
@@ -268,21 +774,28 @@ impl<'a> ScenHeader<'a> {
                 try!(zlib_buf.write_f32::<LE>(p.resources.stone as f32));
                 try!(zlib_buf.write_f32::<LE>(p.resources.ore as f32));
                 try!(zlib_buf.write_f32::<LE>(0.0 /* ??? */));
-                try!(zlib_buf.write_f32::<LE>(0.0 /* population */));
+                if version.has_population() {
+                    try!(zlib_buf.write_f32::<LE>(p.population_cap as f32));
+                }
             } else {
                 // Unused players
-                try!(zlib_buf.write(&vec![0; 7 * mem::size_of::<f32>()]));
+                let fields = if version.has_population() { 7 } else { 6 };
+                try!(zlib_buf.write_all(&vec![0; fields * mem::size_of::<f32>()]));
             }
         }
 
-        for i in 0..9 {
-            // Zero units
-            if i > 0 && self.players.len() >= i {
+        try!(zlib_buf.write_u32::<LE>(self.gaia_units.len() as u32));
+        for unit in &self.gaia_units {
+            try!(zlib_buf.write_all(
+                &try!(unit.to_bytes())
+            ));
+        }
+        for i in 1..9 {
+            if self.players.len() >= i {
                 let units = &self.players[i - 1].units;
-                println!("Units: p{} {}", i, units.len());
                 try!(zlib_buf.write_u32::<LE>(units.len() as u32));
                 for unit in units {
-                    try!(zlib_buf.write(
+                    try!(zlib_buf.write_all(
                         &try!(unit.to_bytes())
                     ));
                 }
@@ -304,90 +817,223 @@ impl<'a> ScenHeader<'a> {
         // Playable players
         try!(zlib_buf.write_u32::<LE>(9));
 
+        let map_center = self.map.size as f32 / 2.0;
         for player in 1..9 {
-            let name = "Promisory";
-            try!(zlib_buf.write_i16::<LE>(name.len() as i16));
-            try!(zlib_buf.write(&name.as_bytes()));
-            try!(zlib_buf.write_f32::<LE>(101.0 /* camera x */));
-            try!(zlib_buf.write_f32::<LE>(101.0 /* camera y */));
-            try!(zlib_buf.write_i16::<LE>(101 /* ?? */));
-            try!(zlib_buf.write_i16::<LE>(101 /* ?? */));
-            try!(zlib_buf.write(&[0])); // allied victory (again?)
-            // Diplomacy again
+            let p = self.players.get(player as usize - 1);
+            let editor_name = p.map(|p| &p.editor_name[..]).unwrap_or("");
+            let (camera_x, camera_y) = p.and_then(|p| p.camera.or_else(|| p.start_position()))
+                .unwrap_or((map_center, map_center));
+            try!(write_str16(&mut zlib_buf, editor_name));
+            try!(zlib_buf.write_f32::<LE>(camera_x));
+            try!(zlib_buf.write_f32::<LE>(camera_y));
+            // Initial camera
+            try!(zlib_buf.write_i16::<LE>(camera_x as i16));
+            try!(zlib_buf.write_i16::<LE>(camera_y as i16));
+            // Allied victory again
+            try!(zlib_buf.write_u8(if self.diplomacy.allied_victory[player as usize - 1] { 1 } else { 0 }));
+            // Diplomacy again, towards gaia and players 1-8
             try!(zlib_buf.write_u16::<LE>(9));
-            try!(zlib_buf.write(&[0; 9]));
-            // Diplo to gaia?? from gaia?
-            for _ in 0..9 {
-                try!(zlib_buf.write_i32::<LE>(0));
+            try!(zlib_buf.write_u8(DiplomacyStance::Enemy as u8));
+            for other in 1..9 {
+                try!(zlib_buf.write_u8(self.diplomacy.stance(player as u8, other) as u8));
+            }
+            // Diplomacy for the AI: 0 is gaia, 1 is the player itself
+            try!(zlib_buf.write_i32::<LE>(0));
+            for other in 1..9 {
+                let stance = if other == player as u8 {
+                    1
+                } else {
+                    self.diplomacy.stance(player as u8, other).ai_stance()
+                };
+                try!(zlib_buf.write_i32::<LE>(stance));
             }
-            // Player colour
-            try!(zlib_buf.write_u32::<LE>(player));
+            try!(zlib_buf.write_u32::<LE>(p.and_then(|p| p.color).unwrap_or(player - 1)));
             // ???
             try!(zlib_buf.write_f32::<LE>(2.0));
             try!(zlib_buf.write_u16::<LE>(0));
             // ???
-            try!(zlib_buf.write(&[0; 8 + 7]));
+            try!(zlib_buf.write_all(&[0; 8 + 7]));
             try!(zlib_buf.write_i32::<LE>(-1));
         }
 
         try!(zlib_buf.write_u32::<LE>(0x9999999A));
         try!(zlib_buf.write_u32::<LE>(0x3FF99999));
-        try!(zlib_buf.write(&[0]));
+        try!(zlib_buf.write_all(&[0]));
         // Triggers
-        try!(zlib_buf.write_i32::<LE>(0));
-
+        try!(zlib_buf.write_i32::<LE>(self.triggers.len() as i32));
+        for trigger in &self.triggers {
+            try!(trigger.write_to(&mut zlib_buf));
+        }
+        let positions: Vec<i32> = self.triggers.iter().map(|trigger| trigger.display_order).collect();
+        try!(write_display_order(&mut zlib_buf, &positions));
         try!(zlib_buf.write_u32::<LE>(0));
         try!(zlib_buf.write_u32::<LE>(0));
 
-        let mut compressed_buf = vec![];
-        compressed_buf.reserve(zlib_buf.len());
-        let mut compressor = Compress::new(Compression::Default, false);
-        match compressor.compress_vec(&zlib_buf, &mut compressed_buf, Flush::Sync) {
-            Status::Ok => println!("hoi"),
-            Status::BufError => panic!("BufError"),
-            Status::StreamEnd => panic!("StreamEnd"),
-        };
+        try!(zlib_buf.finish());
+        Ok(())
+    }
+}
 
-        println!("compressed: {} -> {}", zlib_buf.len(), compressed_buf.len());
+impl ScenMessages {
+    pub fn new() -> ScenMessages {
+        ScenMessages {
+            objectives: String::new(),
+            hints: String::new(),
+            scouts: String::new(),
+            history: String::new(),
+            victory: String::new(),
+            loss: String::new(),
+            objectives_id: 0,
+            hints_id: 0,
+            scouts_id: 0,
+            history_id: 0,
+            victory_id: 0,
+            loss_id: 0,
+        }
+    }
 
-        try!(buf.write_all(&compressed_buf));
-        Ok(buf)
+    fn from_reader<R: Read>(input: &mut R, version: ScenVersion) -> Result<ScenMessages, io::Error> {
+        // String table indices
+        let objectives_id = try!(input.read_i32::<LE>());
+        let hints_id = try!(input.read_i32::<LE>());
+        let victory_id = try!(input.read_i32::<LE>());
+        let loss_id = try!(input.read_i32::<LE>());
+        let history_id = try!(input.read_i32::<LE>());
+        let scouts_id = if version.has_scouts_message() {
+            try!(input.read_i32::<LE>())
+        } else {
+            0
+        };
+        let objectives = try!(ScenMessages::read_message(input, version));
+        let hints = try!(ScenMessages::read_message(input, version));
+        let victory = try!(ScenMessages::read_message(input, version));
+        let loss = try!(ScenMessages::read_message(input, version));
+        let history = try!(ScenMessages::read_message(input, version));
+        let scouts = if version.has_scouts_message() {
+            try!(ScenMessages::read_message(input, version))
+        } else {
+            String::new()
+        };
+        Ok(ScenMessages {
+            objectives: objectives,
+            hints: hints,
+            scouts: scouts,
+            history: history,
+            victory: victory,
+            loss: loss,
+            objectives_id: objectives_id,
+            hints_id: hints_id,
+            scouts_id: scouts_id,
+            history_id: history_id,
+            victory_id: victory_id,
+            loss_id: loss_id,
+        })
     }
-}
 
-impl<'a> ScenMessages<'a> {
-    fn message_to_bytes(buf: &mut Vec<u8>, message: &str) -> Result<(), io::Error> {
-        try!(buf.write_u16::<LE>(1 + (message.len() as u16)));
+    fn read_message<R: Read>(input: &mut R, version: ScenVersion) -> Result<String, io::Error> {
+        if version.has_long_messages() {
+            read_str32(input)
+        } else {
+            read_str16(input)
+        }
+    }
+
+    fn message_to_bytes(buf: &mut Vec<u8>, message: &str, version: ScenVersion) -> Result<(), io::Error> {
+        if version.has_long_messages() {
+            try!(buf.write_i32::<LE>(1 + (message.len() as i32)));
+        } else {
+            if message.len() >= u16::max_value() as usize {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("message is {} bytes long, the maximum is {}", message.len(), u16::max_value() - 1)
+                ));
+            }
+            try!(buf.write_u16::<LE>(1 + (message.len() as u16)));
+        }
         try!(buf.write(&message.as_bytes()));
         try!(buf.write_u8(0));
         Ok(())
     }
 
-    fn to_bytes(&self) -> Result<Vec<u8>, io::Error> {
+    fn to_bytes(&self, version: ScenVersion) -> Result<Vec<u8>, io::Error> {
         let mut buf = vec![];
         // String table indices
-        for _ in 0..6 {
-            try!(buf.write_i32::<LE>(0));
-        }
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.objectives));
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.hints));
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.victory));
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.loss));
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.history));
-        try!(ScenMessages::message_to_bytes(&mut buf, &self.scouts));
+        try!(buf.write_i32::<LE>(self.objectives_id));
+        try!(buf.write_i32::<LE>(self.hints_id));
+        try!(buf.write_i32::<LE>(self.victory_id));
+        try!(buf.write_i32::<LE>(self.loss_id));
+        try!(buf.write_i32::<LE>(self.history_id));
+        if version.has_scouts_message() {
+            try!(buf.write_i32::<LE>(self.scouts_id));
+        }
+        try!(ScenMessages::message_to_bytes(&mut buf, &self.objectives, version));
+        try!(ScenMessages::message_to_bytes(&mut buf, &self.hints, version));
+        try!(ScenMessages::message_to_bytes(&mut buf, &self.victory, version));
+        try!(ScenMessages::message_to_bytes(&mut buf, &self.loss, version));
+        try!(ScenMessages::message_to_bytes(&mut buf, &self.history, version));
+        if version.has_scouts_message() {
+            try!(ScenMessages::message_to_bytes(&mut buf, &self.scouts, version));
+        }
         Ok(buf)
     }
 }
 
-impl<'a> ScenImage<'a> {
+impl ScenCinematics {
+    pub fn new() -> ScenCinematics {
+        ScenCinematics {
+            pregame: String::new(),
+            victory: String::new(),
+            loss: String::new(),
+        }
+    }
+}
+
+impl ScenImage {
+    pub fn new() -> ScenImage {
+        ScenImage {
+            filename: String::new(),
+            bitmap: None,
+            include: 1,
+        }
+    }
+
+    fn from_reader<R: Read>(input: &mut R) -> Result<ScenImage, io::Error> {
+        let filename = try!(read_str16(input));
+        let included = try!(input.read_i32::<LE>()) != 0;
+        // Width and height, also in the bitmap header
+        try!(input.read_i32::<LE>());
+        try!(input.read_i32::<LE>());
+        let include = try!(input.read_i16::<LE>());
+        let bitmap = if included {
+            try!(Bitmap::read_or_skip(input))
+        } else {
+            None
+        };
+        Ok(ScenImage {
+            filename: filename,
+            bitmap: bitmap,
+            include: include,
+        })
+    }
+
     fn to_bytes(&self) -> Result<Vec<u8>, io::Error> {
         let mut buf = vec![];
-        try!(buf.write_u16::<LE>(self.filename.len() as u16));
-        try!(buf.write(&self.filename.as_bytes()));
-        try!(buf.write_i32::<LE>(if self.included { 1 } else { 0 }));
-        try!(buf.write_i32::<LE>(self.width));
-        try!(buf.write_i32::<LE>(self.height));
-        try!(buf.write_i16::<LE>(self.include));
+        try!(write_str16(&mut buf, &self.filename));
+        match self.bitmap {
+            Some(ref bitmap) => {
+                try!(buf.write_i32::<LE>(1));
+                try!(buf.write_i32::<LE>(bitmap.width));
+                try!(buf.write_i32::<LE>(bitmap.height));
+                try!(buf.write_i16::<LE>(self.include));
+                try!(bitmap.write_to(&mut buf));
+            },
+            None => {
+                try!(buf.write_i32::<LE>(0));
+                try!(buf.write_i32::<LE>(0));
+                try!(buf.write_i32::<LE>(0));
+                try!(buf.write_i16::<LE>(self.include));
+            },
+        }
         Ok(buf)
     }
 }
@@ -66,6 +66,17 @@ impl Rectangle {
             height: height
         }
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Whether the rectangles share a tile. Rectangles that only touch do not intersect.
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        !self.is_empty() && !other.is_empty() &&
+            self.x < other.x + other.width && other.x < self.x + self.width &&
+            self.y < other.y + other.height && other.y < self.y + self.height
+    }
 }
 
 impl Selection for Rectangle {
@@ -0,0 +1,360 @@
+use consts::UnknownIdError;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Technology {
+    // Ages
+    FeudalAge = 101,
+    CastleAge = 102,
+    ImperialAge = 103,
+    // Town Center
+    Loom = 22,
+    TownWatch = 8,
+    TownPatrol = 280,
+    Wheelbarrow = 213,
+    HandCart = 249,
+    // Economy
+    HorseCollar = 14,
+    HeavyPlow = 13,
+    CropRotation = 12,
+    DoubleBitAxe = 202,
+    BowSaw = 203,
+    TwoManSaw = 221,
+    GoldMining = 55,
+    GoldShaftMining = 182,
+    StoneMining = 278,
+    StoneShaftMining = 279,
+    Coinage = 23,
+    Banking = 17,
+    Caravan = 48,
+    Guilds = 15,
+    Cartography = 19,
+    // Blacksmith
+    Forging = 67,
+    IronCasting = 68,
+    BlastFurnace = 75,
+    ScaleMailArmor = 74,
+    ChainMailArmor = 76,
+    PlateMailArmor = 77,
+    ScaleBardingArmor = 81,
+    ChainBardingArmor = 82,
+    PlateBardingArmor = 80,
+    Fletching = 199,
+    BodkinArrow = 200,
+    Bracer = 201,
+    PaddedArcherArmor = 211,
+    LeatherArcherArmor = 212,
+    RingArcherArmor = 219,
+    // University
+    Masonry = 50,
+    Architecture = 51,
+    Ballistics = 93,
+    Chemistry = 47,
+    SiegeEngineers = 377,
+    HeatedShot = 380,
+    MurderHoles = 322,
+    TreadmillCrane = 54,
+    GuardTower = 140,
+    Keep = 63,
+    BombardTower = 64,
+    FortifiedWall = 194,
+    // Monastery
+    Redemption = 316,
+    Atonement = 319,
+    Sanctity = 231,
+    Fervor = 252,
+    Illumination = 233,
+    BlockPrinting = 230,
+    Faith = 45,
+    HerbalMedicine = 441,
+    Heresy = 439,
+    Theocracy = 438,
+    // Military
+    Squires = 215,
+    Tracking = 90,
+    ThumbRing = 437,
+    ParthianTactics = 436,
+    Husbandry = 39,
+    Bloodlines = 435,
+    Conscription = 315,
+    Hoardings = 379,
+    Sappers = 321,
+    Spies = 408,
+    Careening = 374,
+    DryDock = 375,
+    Shipwright = 373,
+    // Unit upgrades
+    ManAtArms = 222,
+    LongSwordsman = 207,
+    TwoHandedSwordsman = 217,
+    Champion = 264,
+    Pikeman = 197,
+    Halberdier = 429,
+    Crossbowman = 100,
+    Arbalest = 237,
+    EliteSkirmisher = 98,
+    HeavyCavalryArcher = 218,
+    HandCannoneer = 85,
+    LightCavalry = 254,
+    Hussar = 428,
+    Cavalier = 209,
+    Paladin = 265,
+    HeavyCamel = 236,
+    EliteEagleWarrior = 384,
+    CappedRam = 96,
+    SiegeRam = 255,
+    Onager = 257,
+    SiegeOnager = 320,
+    HeavyScorpion = 239,
+    BombardCannon = 188,
+    WarGalley = 34,
+    Galleon = 35,
+    FastFireShip = 246,
+    HeavyDemolitionShip = 244,
+    EliteCannonGalleon = 376,
+}
+
+impl Technology {
+    pub fn from_u16(id: u16) -> Result<Technology, UnknownIdError> {
+        match id {
+            8 => Ok(Technology::TownWatch),
+            12 => Ok(Technology::CropRotation),
+            13 => Ok(Technology::HeavyPlow),
+            14 => Ok(Technology::HorseCollar),
+            15 => Ok(Technology::Guilds),
+            17 => Ok(Technology::Banking),
+            19 => Ok(Technology::Cartography),
+            22 => Ok(Technology::Loom),
+            23 => Ok(Technology::Coinage),
+            34 => Ok(Technology::WarGalley),
+            35 => Ok(Technology::Galleon),
+            39 => Ok(Technology::Husbandry),
+            45 => Ok(Technology::Faith),
+            47 => Ok(Technology::Chemistry),
+            48 => Ok(Technology::Caravan),
+            50 => Ok(Technology::Masonry),
+            51 => Ok(Technology::Architecture),
+            54 => Ok(Technology::TreadmillCrane),
+            55 => Ok(Technology::GoldMining),
+            63 => Ok(Technology::Keep),
+            64 => Ok(Technology::BombardTower),
+            67 => Ok(Technology::Forging),
+            68 => Ok(Technology::IronCasting),
+            74 => Ok(Technology::ScaleMailArmor),
+            75 => Ok(Technology::BlastFurnace),
+            76 => Ok(Technology::ChainMailArmor),
+            77 => Ok(Technology::PlateMailArmor),
+            80 => Ok(Technology::PlateBardingArmor),
+            81 => Ok(Technology::ScaleBardingArmor),
+            82 => Ok(Technology::ChainBardingArmor),
+            85 => Ok(Technology::HandCannoneer),
+            90 => Ok(Technology::Tracking),
+            93 => Ok(Technology::Ballistics),
+            96 => Ok(Technology::CappedRam),
+            98 => Ok(Technology::EliteSkirmisher),
+            100 => Ok(Technology::Crossbowman),
+            101 => Ok(Technology::FeudalAge),
+            102 => Ok(Technology::CastleAge),
+            103 => Ok(Technology::ImperialAge),
+            140 => Ok(Technology::GuardTower),
+            182 => Ok(Technology::GoldShaftMining),
+            188 => Ok(Technology::BombardCannon),
+            194 => Ok(Technology::FortifiedWall),
+            197 => Ok(Technology::Pikeman),
+            199 => Ok(Technology::Fletching),
+            200 => Ok(Technology::BodkinArrow),
+            201 => Ok(Technology::Bracer),
+            202 => Ok(Technology::DoubleBitAxe),
+            203 => Ok(Technology::BowSaw),
+            207 => Ok(Technology::LongSwordsman),
+            209 => Ok(Technology::Cavalier),
+            211 => Ok(Technology::PaddedArcherArmor),
+            212 => Ok(Technology::LeatherArcherArmor),
+            213 => Ok(Technology::Wheelbarrow),
+            215 => Ok(Technology::Squires),
+            217 => Ok(Technology::TwoHandedSwordsman),
+            218 => Ok(Technology::HeavyCavalryArcher),
+            219 => Ok(Technology::RingArcherArmor),
+            221 => Ok(Technology::TwoManSaw),
+            222 => Ok(Technology::ManAtArms),
+            230 => Ok(Technology::BlockPrinting),
+            231 => Ok(Technology::Sanctity),
+            233 => Ok(Technology::Illumination),
+            236 => Ok(Technology::HeavyCamel),
+            237 => Ok(Technology::Arbalest),
+            239 => Ok(Technology::HeavyScorpion),
+            244 => Ok(Technology::HeavyDemolitionShip),
+            246 => Ok(Technology::FastFireShip),
+            249 => Ok(Technology::HandCart),
+            252 => Ok(Technology::Fervor),
+            254 => Ok(Technology::LightCavalry),
+            255 => Ok(Technology::SiegeRam),
+            257 => Ok(Technology::Onager),
+            264 => Ok(Technology::Champion),
+            265 => Ok(Technology::Paladin),
+            278 => Ok(Technology::StoneMining),
+            279 => Ok(Technology::StoneShaftMining),
+            280 => Ok(Technology::TownPatrol),
+            315 => Ok(Technology::Conscription),
+            316 => Ok(Technology::Redemption),
+            319 => Ok(Technology::Atonement),
+            320 => Ok(Technology::SiegeOnager),
+            321 => Ok(Technology::Sappers),
+            322 => Ok(Technology::MurderHoles),
+            373 => Ok(Technology::Shipwright),
+            374 => Ok(Technology::Careening),
+            375 => Ok(Technology::DryDock),
+            376 => Ok(Technology::EliteCannonGalleon),
+            377 => Ok(Technology::SiegeEngineers),
+            379 => Ok(Technology::Hoardings),
+            380 => Ok(Technology::HeatedShot),
+            384 => Ok(Technology::EliteEagleWarrior),
+            408 => Ok(Technology::Spies),
+            428 => Ok(Technology::Hussar),
+            429 => Ok(Technology::Halberdier),
+            435 => Ok(Technology::Bloodlines),
+            436 => Ok(Technology::ParthianTactics),
+            437 => Ok(Technology::ThumbRing),
+            438 => Ok(Technology::Theocracy),
+            439 => Ok(Technology::Heresy),
+            441 => Ok(Technology::HerbalMedicine),
+            _ => Err(UnknownIdError { kind: "technology", id: id as u32 }),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Technology::FeudalAge => "Feudal Age",
+            Technology::CastleAge => "Castle Age",
+            Technology::ImperialAge => "Imperial Age",
+            Technology::Loom => "Loom",
+            Technology::TownWatch => "Town Watch",
+            Technology::TownPatrol => "Town Patrol",
+            Technology::Wheelbarrow => "Wheelbarrow",
+            Technology::HandCart => "Hand Cart",
+            Technology::HorseCollar => "Horse Collar",
+            Technology::HeavyPlow => "Heavy Plow",
+            Technology::CropRotation => "Crop Rotation",
+            Technology::DoubleBitAxe => "Double-Bit Axe",
+            Technology::BowSaw => "Bow Saw",
+            Technology::TwoManSaw => "Two-Man Saw",
+            Technology::GoldMining => "Gold Mining",
+            Technology::GoldShaftMining => "Gold Shaft Mining",
+            Technology::StoneMining => "Stone Mining",
+            Technology::StoneShaftMining => "Stone Shaft Mining",
+            Technology::Coinage => "Coinage",
+            Technology::Banking => "Banking",
+            Technology::Caravan => "Caravan",
+            Technology::Guilds => "Guilds",
+            Technology::Cartography => "Cartography",
+            Technology::Forging => "Forging",
+            Technology::IronCasting => "Iron Casting",
+            Technology::BlastFurnace => "Blast Furnace",
+            Technology::ScaleMailArmor => "Scale Mail Armor",
+            Technology::ChainMailArmor => "Chain Mail Armor",
+            Technology::PlateMailArmor => "Plate Mail Armor",
+            Technology::ScaleBardingArmor => "Scale Barding Armor",
+            Technology::ChainBardingArmor => "Chain Barding Armor",
+            Technology::PlateBardingArmor => "Plate Barding Armor",
+            Technology::Fletching => "Fletching",
+            Technology::BodkinArrow => "Bodkin Arrow",
+            Technology::Bracer => "Bracer",
+            Technology::PaddedArcherArmor => "Padded Archer Armor",
+            Technology::LeatherArcherArmor => "Leather Archer Armor",
+            Technology::RingArcherArmor => "Ring Archer Armor",
+            Technology::Masonry => "Masonry",
+            Technology::Architecture => "Architecture",
+            Technology::Ballistics => "Ballistics",
+            Technology::Chemistry => "Chemistry",
+            Technology::SiegeEngineers => "Siege Engineers",
+            Technology::HeatedShot => "Heated Shot",
+            Technology::MurderHoles => "Murder Holes",
+            Technology::TreadmillCrane => "Treadmill Crane",
+            Technology::GuardTower => "Guard Tower",
+            Technology::Keep => "Keep",
+            Technology::BombardTower => "Bombard Tower",
+            Technology::FortifiedWall => "Fortified Wall",
+            Technology::Redemption => "Redemption",
+            Technology::Atonement => "Atonement",
+            Technology::Sanctity => "Sanctity",
+            Technology::Fervor => "Fervor",
+            Technology::Illumination => "Illumination",
+            Technology::BlockPrinting => "Block Printing",
+            Technology::Faith => "Faith",
+            Technology::HerbalMedicine => "Herbal Medicine",
+            Technology::Heresy => "Heresy",
+            Technology::Theocracy => "Theocracy",
+            Technology::Squires => "Squires",
+            Technology::Tracking => "Tracking",
+            Technology::ThumbRing => "Thumb Ring",
+            Technology::ParthianTactics => "Parthian Tactics",
+            Technology::Husbandry => "Husbandry",
+            Technology::Bloodlines => "Bloodlines",
+            Technology::Conscription => "Conscription",
+            Technology::Hoardings => "Hoardings",
+            Technology::Sappers => "Sappers",
+            Technology::Spies => "Spies",
+            Technology::Careening => "Careening",
+            Technology::DryDock => "Dry Dock",
+            Technology::Shipwright => "Shipwright",
+            Technology::ManAtArms => "Man-at-Arms",
+            Technology::LongSwordsman => "Long Swordsman",
+            Technology::TwoHandedSwordsman => "Two-Handed Swordsman",
+            Technology::Champion => "Champion",
+            Technology::Pikeman => "Pikeman",
+            Technology::Halberdier => "Halberdier",
+            Technology::Crossbowman => "Crossbowman",
+            Technology::Arbalest => "Arbalest",
+            Technology::EliteSkirmisher => "Elite Skirmisher",
+            Technology::HeavyCavalryArcher => "Heavy Cavalry Archer",
+            Technology::HandCannoneer => "Hand Cannoneer",
+            Technology::LightCavalry => "Light Cavalry",
+            Technology::Hussar => "Hussar",
+            Technology::Cavalier => "Cavalier",
+            Technology::Paladin => "Paladin",
+            Technology::HeavyCamel => "Heavy Camel",
+            Technology::EliteEagleWarrior => "Elite Eagle Warrior",
+            Technology::CappedRam => "Capped Ram",
+            Technology::SiegeRam => "Siege Ram",
+            Technology::Onager => "Onager",
+            Technology::SiegeOnager => "Siege Onager",
+            Technology::HeavyScorpion => "Heavy Scorpion",
+            Technology::BombardCannon => "Bombard Cannon",
+            Technology::WarGalley => "War Galley",
+            Technology::Galleon => "Galleon",
+            Technology::FastFireShip => "Fast Fire Ship",
+            Technology::HeavyDemolitionShip => "Heavy Demolition Ship",
+            Technology::EliteCannonGalleon => "Elite Cannon Galleon",
+        }
+    }
+}
+
+/// A technology: one of the known `Technology`s, or a raw ID for technologies that
+/// `Technology` does not list.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TechnologyId {
+    Known(Technology),
+    Raw(u16),
+}
+
+impl TechnologyId {
+    // Never fails: IDs that are not in `Technology` become `Raw`.
+    pub fn from_u16(id: u16) -> TechnologyId {
+        match Technology::from_u16(id) {
+            Ok(tech) => TechnologyId::Known(tech),
+            Err(_) => TechnologyId::Raw(id),
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        match *self {
+            TechnologyId::Known(tech) => tech as u16,
+            TechnologyId::Raw(id) => id,
+        }
+    }
+}
+
+impl From<Technology> for TechnologyId {
+    fn from(tech: Technology) -> TechnologyId {
+        TechnologyId::Known(tech)
+    }
+}
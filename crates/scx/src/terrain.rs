@@ -0,0 +1,184 @@
+use consts::UnknownIdError;
+use version::ScenVersion;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Terrain {
+    Grass1 = 0,
+    WaterShallow = 1,
+    Beach = 2,
+    Dirt3 = 3,
+    Shallows = 4,
+    Leaves = 5,
+    Dirt1 = 6,
+    Farm = 7,
+    DeadFarm = 8,
+    Grass3 = 9,
+    Forest = 10,
+    Dirt2 = 11,
+    Grass2 = 12,
+    PalmDesert = 13,
+    Desert = 14,
+    WaterNotDockable = 15,
+    GrassOther = 16,
+    Jungle = 17,
+    Bamboo = 18,
+    PineForest = 19,
+    OakForest = 20,
+    SnowPineForest = 21,
+    WaterDeep = 22,
+    WaterMedium = 23,
+    Road = 24,
+    RoadBroken = 25,
+    Ice = 26,
+    Foundation = 27,
+    WaterBridge = 28,
+    FarmConstruction1 = 29,
+    FarmConstruction2 = 30,
+    FarmConstruction3 = 31,
+    Snow = 32,
+    SnowDirt = 33,
+    SnowGrass = 34,
+    Ice2 = 35,
+    SnowFoundation = 36,
+    IceBeach = 37,
+    RoadSnow = 38,
+    RoadFungus = 39,
+    RockRoad = 40,
+}
+
+/// Editor data for a terrain type.
+#[derive(Clone, Copy, Debug)]
+pub struct TerrainInfo {
+    pub terrain: Terrain,
+    pub name: &'static str,
+    /// Key in the `Arms.terrain` Lua table.
+    pub lua_name: &'static str,
+    pub land_passable: bool,
+    pub water_passable: bool,
+    pub buildable: bool,
+    /// The first game version that has this terrain.
+    pub since: ScenVersion,
+}
+
+macro_rules! terrains {
+    ($($terrain:ident, $name:expr, $lua_name:expr, $land:expr, $water:expr, $buildable:expr, $since:ident;)*) => {
+        static TERRAINS: &'static [TerrainInfo] = &[
+            $(TerrainInfo {
+                terrain: Terrain::$terrain,
+                name: $name,
+                lua_name: $lua_name,
+                land_passable: $land,
+                water_passable: $water,
+                buildable: $buildable,
+                since: ScenVersion::$since,
+            },)*
+        ];
+    }
+}
+
+// Sorted by ID, so a terrain's entry is at its own index.
+terrains! {
+    // Variant         Name                    Lua name              Land   Water  Build  Since
+    Grass1,            "Grass 1",              "grass1",             true,  false, true,  AoK;
+    WaterShallow,      "Water, Shallow",       "water_shallow",      false, true,  false, AoK;
+    Beach,             "Beach",                "beach",              true,  false, false, AoK;
+    Dirt3,             "Dirt 3",               "dirt3",              true,  false, true,  AoK;
+    Shallows,          "Shallows",             "shallows",           true,  false, false, AoK;
+    Leaves,            "Leaves",               "leaves",             true,  false, true,  AoK;
+    Dirt1,             "Dirt 1",               "dirt1",              true,  false, true,  AoK;
+    Farm,              "Farm",                 "farm",               true,  false, false, AoK;
+    DeadFarm,          "Dead Farm",            "dead_farm",          true,  false, false, AoK;
+    Grass3,            "Grass 3",              "grass3",             true,  false, true,  AoK;
+    Forest,            "Forest",               "forest",             false, false, false, AoK;
+    Dirt2,             "Dirt 2",               "dirt2",              true,  false, true,  AoK;
+    Grass2,            "Grass 2",              "grass2",             true,  false, true,  AoK;
+    PalmDesert,        "Palm Desert",          "palm_desert",        false, false, false, AoK;
+    Desert,            "Desert",               "desert",             true,  false, true,  AoK;
+    WaterNotDockable,  "Water, Not Dockable",  "water_not_dockable", false, true,  false, AoK;
+    GrassOther,        "Grass, Other",         "grass_other",        true,  false, true,  AoK;
+    Jungle,            "Jungle",               "jungle",             false, false, false, AoK;
+    Bamboo,            "Bamboo",               "bamboo",             false, false, false, AoK;
+    PineForest,        "Pine Forest",          "pine_forest",        false, false, false, AoK;
+    OakForest,         "Oak Forest",           "forest_oak",         false, false, false, AoK;
+    SnowPineForest,    "Snow Pine Forest",     "snow_pine_forest",   false, false, false, AoK;
+    WaterDeep,         "Water, Deep",          "water_deep",         false, true,  false, AoK;
+    WaterMedium,       "Water, Medium",        "water_medium",       false, true,  false, AoK;
+    Road,              "Road",                 "road",               true,  false, true,  AoK;
+    RoadBroken,        "Road, Broken",         "road_broken",        true,  false, true,  AoK;
+    Ice,               "Ice",                  "ice",                true,  false, false, AoK;
+    Foundation,        "Foundation",           "foundation",         true,  false, true,  AoK;
+    WaterBridge,       "Water, Bridge",        "water_bridge",       true,  false, false, AoK;
+    FarmConstruction1, "Farm Construction 1",  "farm_construction1", true,  false, false, AoK;
+    FarmConstruction2, "Farm Construction 2",  "farm_construction2", true,  false, false, AoK;
+    FarmConstruction3, "Farm Construction 3",  "farm_construction3", true,  false, false, AoK;
+    Snow,              "Snow",                 "snow",               true,  false, true,  AoC;
+    SnowDirt,          "Snow, Dirt",           "snow_dirt",          true,  false, true,  AoC;
+    SnowGrass,         "Snow, Grass",          "snow_grass",         true,  false, true,  AoC;
+    Ice2,              "Ice 2",                "ice2",               true,  false, false, AoC;
+    SnowFoundation,    "Snow Foundation",      "snow_foundation",    true,  false, true,  AoC;
+    IceBeach,          "Ice, Beach",           "ice_beach",          true,  false, false, AoC;
+    RoadSnow,          "Road, Snow",           "road_snow",          true,  false, true,  AoC;
+    RoadFungus,        "Road, Fungus",         "road_fungus",        true,  false, true,  AoC;
+    RockRoad,          "Rock Road",            "rock_road",          true,  false, true,  AoC;
+}
+
+// Names from the old `Terrain` enum, which did not match the game's terrain IDs. Names that
+// are still variants now have the right ID; the others point at the closest terrain.
+#[allow(non_upper_case_globals)]
+impl Terrain {
+    #[deprecated(note = "use Terrain::Grass1")]
+    pub const Grass: Terrain = Terrain::Grass1;
+    #[deprecated(note = "use Terrain::WaterShallow")]
+    pub const Water: Terrain = Terrain::WaterShallow;
+    #[deprecated(note = "use Terrain::Dirt1")]
+    pub const Dirt: Terrain = Terrain::Dirt1;
+    #[deprecated(note = "use Terrain::Farm")]
+    pub const Farm1: Terrain = Terrain::Farm;
+    #[deprecated(note = "use Terrain::DeadFarm")]
+    pub const Farm2: Terrain = Terrain::DeadFarm;
+    #[deprecated(note = "use Terrain::SnowPineForest")]
+    pub const SnowForest: Terrain = Terrain::SnowPineForest;
+    #[deprecated(note = "use Terrain::WaterDeep")]
+    pub const Water2: Terrain = Terrain::WaterDeep;
+    #[deprecated(note = "use Terrain::WaterMedium")]
+    pub const Water3: Terrain = Terrain::WaterMedium;
+    #[deprecated(note = "use Terrain::RoadBroken")]
+    pub const Road2: Terrain = Terrain::RoadBroken;
+    #[deprecated(note = "use Terrain::FarmConstruction1")]
+    pub const FarmCnst1: Terrain = Terrain::FarmConstruction1;
+    #[deprecated(note = "use Terrain::FarmConstruction2")]
+    pub const FarmCnst2: Terrain = Terrain::FarmConstruction2;
+    #[deprecated(note = "use Terrain::FarmConstruction3")]
+    pub const FarmCnst3: Terrain = Terrain::FarmConstruction3;
+    #[deprecated(note = "use Terrain::RoadSnow")]
+    pub const SnowRoad: Terrain = Terrain::RoadSnow;
+    #[deprecated(note = "use Terrain::RoadFungus")]
+    pub const SnowRoad2: Terrain = Terrain::RoadFungus;
+    #[deprecated(note = "use Terrain::Foundation")]
+    pub const KOH: Terrain = Terrain::Foundation;
+}
+
+impl Terrain {
+    pub fn from_u8(id: u8) -> Result<Terrain, UnknownIdError> {
+        TERRAINS.get(id as usize)
+            .map(|info| info.terrain)
+            .ok_or(UnknownIdError { kind: "terrain", id: id as u32 })
+    }
+
+    /// All known terrains, sorted by ID.
+    pub fn all() -> &'static [TerrainInfo] {
+        TERRAINS
+    }
+
+    pub fn info(&self) -> &'static TerrainInfo {
+        &TERRAINS[*self as usize]
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn is_available_in(&self, version: ScenVersion) -> bool {
+        version.data_version() >= self.info().since.data_version()
+    }
+}
@@ -1,4 +1,8 @@
+use std::io::{Read, Write, Error, ErrorKind};
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
+
 use selection::{Coordinate, Rectangle};
+use util::{read_str32, write_str32, skip};
 
 pub struct TriggerCondition {
     pub condition: i32,
@@ -13,10 +17,15 @@ pub struct TriggerCondition {
     pub timer: i32,
     pub area: Rectangle,
     pub unit_group: i32,
+    pub object_type: i32,
     pub ai_signal: i32,
+    /// Fields after `ai_signal` that newer versions store.
+    pub extra_fields: Vec<i32>,
+    /// Position in the editor's condition list.
+    pub display_order: i32,
 }
 
-pub struct TriggerEffect<'a> {
+pub struct TriggerEffect {
     pub effect: i32,
     pub check: i32,
     pub ai_goal: i32,
@@ -36,17 +45,352 @@ pub struct TriggerEffect<'a> {
     pub unit_group: i32,
     pub object_type: i32,
     pub instruction_panel: i32,
-    pub text: &'a str,
-    pub sound_filename: &'a str,
-    pub unit_ids: Vec<i32>,
+    /// Fields after `instruction_panel` that newer versions store.
+    pub extra_fields: Vec<i32>,
+    pub text: String,
+    pub sound_filename: String,
+    /// The selected units. `None` is stored as -1, which the editor uses for effects without
+    /// a selection.
+    pub unit_ids: Option<Vec<i32>>,
+    /// Position in the editor's effect list.
+    pub display_order: i32,
 }
 
-pub struct Trigger<'a> {
+pub struct Trigger {
     pub enabled: bool,
     pub is_looping: bool,
     pub is_objective: bool,
-    pub name: &'a str,
-    pub description: &'a str,
+    pub objective_order: i32,
+    pub name: String,
+    pub description: String,
+    /// language.dll string ID to show instead of the description, or -1 to show the text. Only
+    /// Definitive Edition stores this, so writing fails if it is set.
+    pub description_id: i32,
+    /// Only Definitive Edition stores this, so writing fails if it is set.
+    pub short_description: String,
+    /// Like `description_id`, for the short description.
+    pub short_description_id: i32,
+    /// Show the short description on screen. Writing fails if it is set, like
+    /// `short_description`.
+    pub display_on_screen: bool,
+    /// Position in the editor's trigger list.
+    pub display_order: i32,
     pub conditions: Vec<TriggerCondition>,
-    pub effects: Vec<TriggerEffect<'a>>,
+    pub effects: Vec<TriggerEffect>,
+}
+
+// Number of fields after `check` in AoK and AoC. Later versions store more fields, and
+// set `check` to the number of fields they store.
+const CONDITION_FIELDS: i32 = 16;
+const EFFECT_FIELDS: i32 = 23;
+
+fn read_extra_fields<R: Read>(input: &mut R, check: i32, known: i32) -> Result<Vec<i32>, Error> {
+    let mut fields = vec![];
+    for _ in known..check {
+        fields.push(try!(input.read_i32::<LE>()));
+    }
+    Ok(fields)
+}
+
+// Areas are stored as inclusive x1/y1/x2/y2 coordinates, where -1 means "no area".
+fn read_area<R: Read>(input: &mut R) -> Result<Rectangle, Error> {
+    let x1 = try!(input.read_i32::<LE>()) as u32;
+    let y1 = try!(input.read_i32::<LE>()) as u32;
+    let x2 = try!(input.read_i32::<LE>()) as u32;
+    let y2 = try!(input.read_i32::<LE>()) as u32;
+    Ok(Rectangle::new(x1, y1, x2.wrapping_sub(x1), y2.wrapping_sub(y1)))
+}
+
+fn write_area<W: Write>(output: &mut W, area: &Rectangle) -> Result<(), Error> {
+    try!(output.write_i32::<LE>(area.x as i32));
+    try!(output.write_i32::<LE>(area.y as i32));
+    try!(output.write_i32::<LE>(area.x.wrapping_add(area.width) as i32));
+    try!(output.write_i32::<LE>(area.y.wrapping_add(area.height) as i32));
+    Ok(())
+}
+
+/// Read a display order list, and return the display position of each item.
+pub fn read_display_order<R: Read>(input: &mut R, len: usize) -> Result<Vec<i32>, Error> {
+    let mut positions: Vec<i32> = (0..len as i32).collect();
+    for position in 0..len {
+        let index = try!(input.read_i32::<LE>());
+        if index >= 0 && (index as usize) < len {
+            positions[index as usize] = position as i32;
+        }
+    }
+    Ok(positions)
+}
+
+/// Write the indices of items sorted by their display position. Items with the same position
+/// keep their original order.
+pub fn write_display_order<W: Write>(output: &mut W, positions: &[i32]) -> Result<(), Error> {
+    let mut indices: Vec<usize> = (0..positions.len()).collect();
+    indices.sort_by_key(|&index| (positions[index], index));
+    for index in indices {
+        try!(output.write_i32::<LE>(index as i32));
+    }
+    Ok(())
+}
+
+impl TriggerCondition {
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<TriggerCondition, Error> {
+        let condition = try!(input.read_i32::<LE>());
+        let check = try!(input.read_i32::<LE>());
+        let amount = try!(input.read_i32::<LE>());
+        let resource = try!(input.read_i32::<LE>());
+        let unit_object = try!(input.read_i32::<LE>());
+        let unit_location = try!(input.read_i32::<LE>());
+        let unit_type = try!(input.read_i32::<LE>());
+        let player = try!(input.read_i32::<LE>());
+        let technology = try!(input.read_i32::<LE>());
+        let timer = try!(input.read_i32::<LE>());
+        // ???
+        try!(input.read_i32::<LE>());
+        let area = try!(read_area(input));
+        let unit_group = try!(input.read_i32::<LE>());
+        let object_type = try!(input.read_i32::<LE>());
+        let ai_signal = try!(input.read_i32::<LE>());
+        let extra_fields = try!(read_extra_fields(input, check, CONDITION_FIELDS));
+        Ok(TriggerCondition {
+            condition: condition,
+            check: check,
+            amount: amount,
+            resource: resource,
+            unit_object: unit_object,
+            unit_location: unit_location,
+            unit_type: unit_type,
+            player: player,
+            technology: technology,
+            timer: timer,
+            area: area,
+            unit_group: unit_group,
+            object_type: object_type,
+            ai_signal: ai_signal,
+            extra_fields: extra_fields,
+            display_order: 0,
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        try!(output.write_i32::<LE>(self.condition));
+        try!(output.write_i32::<LE>(CONDITION_FIELDS + self.extra_fields.len() as i32));
+        try!(output.write_i32::<LE>(self.amount));
+        try!(output.write_i32::<LE>(self.resource));
+        try!(output.write_i32::<LE>(self.unit_object));
+        try!(output.write_i32::<LE>(self.unit_location));
+        try!(output.write_i32::<LE>(self.unit_type));
+        try!(output.write_i32::<LE>(self.player));
+        try!(output.write_i32::<LE>(self.technology));
+        try!(output.write_i32::<LE>(self.timer));
+        // ???
+        try!(output.write_i32::<LE>(-1));
+        try!(write_area(output, &self.area));
+        try!(output.write_i32::<LE>(self.unit_group));
+        try!(output.write_i32::<LE>(self.object_type));
+        try!(output.write_i32::<LE>(self.ai_signal));
+        for &field in &self.extra_fields {
+            try!(output.write_i32::<LE>(field));
+        }
+        Ok(())
+    }
+}
+
+impl TriggerEffect {
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<TriggerEffect, Error> {
+        let effect = try!(input.read_i32::<LE>());
+        let check = try!(input.read_i32::<LE>());
+        let ai_goal = try!(input.read_i32::<LE>());
+        let amount = try!(input.read_i32::<LE>());
+        let resource = try!(input.read_i32::<LE>());
+        let diplomacy = try!(input.read_i32::<LE>());
+        let num_units = try!(input.read_i32::<LE>());
+        let unit_location = try!(input.read_i32::<LE>());
+        let unit_type = try!(input.read_i32::<LE>());
+        let player_source = try!(input.read_i32::<LE>());
+        let player_target = try!(input.read_i32::<LE>());
+        let technology = try!(input.read_i32::<LE>());
+        let text_id = try!(input.read_i32::<LE>());
+        // ???
+        try!(input.read_i32::<LE>());
+        let display_time = try!(input.read_i32::<LE>());
+        let trigger_index = try!(input.read_i32::<LE>());
+        let location_x = try!(input.read_i32::<LE>()) as u32;
+        let location_y = try!(input.read_i32::<LE>()) as u32;
+        let area = try!(read_area(input));
+        let unit_group = try!(input.read_i32::<LE>());
+        let object_type = try!(input.read_i32::<LE>());
+        let instruction_panel = try!(input.read_i32::<LE>());
+        let extra_fields = try!(read_extra_fields(input, check, EFFECT_FIELDS));
+        let text = try!(read_str32(input));
+        let sound_filename = try!(read_str32(input));
+        let unit_ids = match num_units {
+            -1 => None,
+            num_units if num_units >= 0 => {
+                let mut unit_ids = vec![];
+                for _ in 0..num_units {
+                    unit_ids.push(try!(input.read_i32::<LE>()));
+                }
+                Some(unit_ids)
+            },
+            _ => return Err(Error::new(ErrorKind::InvalidData, format!("invalid unit count {}", num_units))),
+        };
+        Ok(TriggerEffect {
+            effect: effect,
+            check: check,
+            ai_goal: ai_goal,
+            amount: amount,
+            resource: resource,
+            diplomacy: diplomacy,
+            unit_location: unit_location,
+            unit_type: unit_type,
+            player_source: player_source,
+            player_target: player_target,
+            technology: technology,
+            text_id: text_id,
+            display_time: display_time,
+            trigger_index: trigger_index,
+            location: Coordinate::new(location_x, location_y),
+            area: area,
+            unit_group: unit_group,
+            object_type: object_type,
+            instruction_panel: instruction_panel,
+            extra_fields: extra_fields,
+            text: text,
+            sound_filename: sound_filename,
+            unit_ids: unit_ids,
+            display_order: 0,
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        try!(output.write_i32::<LE>(self.effect));
+        try!(output.write_i32::<LE>(EFFECT_FIELDS + self.extra_fields.len() as i32));
+        try!(output.write_i32::<LE>(self.ai_goal));
+        try!(output.write_i32::<LE>(self.amount));
+        try!(output.write_i32::<LE>(self.resource));
+        try!(output.write_i32::<LE>(self.diplomacy));
+        try!(output.write_i32::<LE>(self.unit_ids.as_ref().map(|ids| ids.len() as i32).unwrap_or(-1)));
+        try!(output.write_i32::<LE>(self.unit_location));
+        try!(output.write_i32::<LE>(self.unit_type));
+        try!(output.write_i32::<LE>(self.player_source));
+        try!(output.write_i32::<LE>(self.player_target));
+        try!(output.write_i32::<LE>(self.technology));
+        try!(output.write_i32::<LE>(self.text_id));
+        // ???
+        try!(output.write_i32::<LE>(-1));
+        try!(output.write_i32::<LE>(self.display_time));
+        try!(output.write_i32::<LE>(self.trigger_index));
+        try!(output.write_i32::<LE>(self.location.x as i32));
+        try!(output.write_i32::<LE>(self.location.y as i32));
+        try!(write_area(output, &self.area));
+        try!(output.write_i32::<LE>(self.unit_group));
+        try!(output.write_i32::<LE>(self.object_type));
+        try!(output.write_i32::<LE>(self.instruction_panel));
+        for &field in &self.extra_fields {
+            try!(output.write_i32::<LE>(field));
+        }
+        try!(write_str32(output, &self.text));
+        try!(write_str32(output, &self.sound_filename));
+        if let Some(ref unit_ids) = self.unit_ids {
+            for id in unit_ids {
+                try!(output.write_i32::<LE>(*id));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Trigger {
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<Trigger, Error> {
+        let enabled = try!(input.read_u32::<LE>()) != 0;
+        let is_looping = try!(input.read_u32::<LE>()) != 0;
+        // ???
+        try!(input.read_u8());
+        let is_objective = try!(input.read_u8()) != 0;
+        let objective_order = try!(input.read_i32::<LE>());
+        // ???
+        try!(skip(input, 4));
+        let description = try!(read_str32(input));
+        let name = try!(read_str32(input));
+        let (conditions, effects) = try!(Trigger::read_contents(input));
+        Ok(Trigger {
+            enabled: enabled,
+            is_looping: is_looping,
+            is_objective: is_objective,
+            objective_order: objective_order,
+            name: name,
+            description: description,
+            description_id: -1,
+            short_description: String::new(),
+            short_description_id: -1,
+            display_on_screen: false,
+            display_order: 0,
+            conditions: conditions,
+            effects: effects,
+        })
+    }
+
+    fn read_contents<R: Read>(input: &mut R) -> Result<(Vec<TriggerCondition>, Vec<TriggerEffect>), Error> {
+        let num_effects = try!(input.read_i32::<LE>());
+        let mut effects = vec![];
+        for _ in 0..num_effects {
+            effects.push(try!(TriggerEffect::from_reader(input)));
+        }
+        let positions = try!(read_display_order(input, effects.len()));
+        for (effect, position) in effects.iter_mut().zip(positions) {
+            effect.display_order = position;
+        }
+
+        let num_conditions = try!(input.read_i32::<LE>());
+        let mut conditions = vec![];
+        for _ in 0..num_conditions {
+            conditions.push(try!(TriggerCondition::from_reader(input)));
+        }
+        let positions = try!(read_display_order(input, conditions.len()));
+        for (condition, position) in conditions.iter_mut().zip(positions) {
+            condition.display_order = position;
+        }
+
+        Ok((conditions, effects))
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        if self.description_id != -1 || self.short_description_id != -1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "this version cannot store string IDs for trigger descriptions"
+            ));
+        }
+        if !self.short_description.is_empty() || self.display_on_screen {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "this version cannot store short trigger descriptions"
+            ));
+        }
+        try!(output.write_u32::<LE>(if self.enabled { 1 } else { 0 }));
+        try!(output.write_u32::<LE>(if self.is_looping { 1 } else { 0 }));
+        // ???
+        try!(output.write_u8(0));
+        try!(output.write_u8(if self.is_objective { 1 } else { 0 }));
+        try!(output.write_i32::<LE>(self.objective_order));
+        // ???
+        try!(output.write_u32::<LE>(0));
+        try!(write_str32(output, &self.description));
+        try!(write_str32(output, &self.name));
+
+        try!(output.write_i32::<LE>(self.effects.len() as i32));
+        for effect in &self.effects {
+            try!(effect.write_to(output));
+        }
+        let positions: Vec<i32> = self.effects.iter().map(|effect| effect.display_order).collect();
+        try!(write_display_order(output, &positions));
+
+        try!(output.write_i32::<LE>(self.conditions.len() as i32));
+        for condition in &self.conditions {
+            try!(condition.write_to(output));
+        }
+        let positions: Vec<i32> = self.conditions.iter().map(|condition| condition.display_order).collect();
+        try!(write_display_order(output, &positions));
+        Ok(())
+    }
 }
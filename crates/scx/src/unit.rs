@@ -1,11 +1,52 @@
-use std::io::Error;
-use byteorder::{LittleEndian as LE, WriteBytesExt};
+use std::io::{Read, Error};
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
 
 use consts::UnitType;
+use selection::Rectangle;
+use unit_info::UnitInfo;
+
+/// The type of a unit: one of the known `UnitType`s, or a raw ID for objects that
+/// `UnitType` does not list, eg. from a mod.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum UnitTypeId {
+    Known(UnitType),
+    Raw(u16),
+}
+
+impl UnitTypeId {
+    // Never fails: IDs that are not in `UnitType` become `Raw`.
+    pub fn from_u16(id: u16) -> UnitTypeId {
+        match UnitType::from_u16(id) {
+            Ok(unit_type) => UnitTypeId::Known(unit_type),
+            Err(_) => UnitTypeId::Raw(id),
+        }
+    }
+
+    // Raw IDs have no metadata.
+    pub fn info(&self) -> Option<UnitInfo> {
+        match *self {
+            UnitTypeId::Known(unit_type) => Some(unit_type.info()),
+            UnitTypeId::Raw(_) => None,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        match *self {
+            UnitTypeId::Known(unit_type) => unit_type as u16,
+            UnitTypeId::Raw(id) => id,
+        }
+    }
+}
+
+impl From<UnitType> for UnitTypeId {
+    fn from(unit_type: UnitType) -> UnitTypeId {
+        UnitTypeId::Known(unit_type)
+    }
+}
 
 pub struct Unit {
     pub id: u32,
-    pub unit_type: UnitType,
+    pub unit_type: UnitTypeId,
     pub x: f32,
     pub y: f32,
     pub angle: f32,
@@ -14,10 +55,10 @@ pub struct Unit {
 }
 
 impl Unit {
-    pub fn new(unit_type: UnitType, pos_x: f32, pos_y: f32) -> Unit {
+    pub fn new<T: Into<UnitTypeId>>(unit_type: T, pos_x: f32, pos_y: f32) -> Unit {
         Unit {
             id: 1,
-            unit_type: unit_type,
+            unit_type: unit_type.into(),
             x: pos_x,
             y: pos_y,
             angle: 0.0,
@@ -26,17 +67,51 @@ impl Unit {
         }
     }
 
+    /// The tiles covered by this unit, centered on its position.
+    pub fn footprint(&self) -> Option<Rectangle> {
+        self.unit_type.info().map(|info| {
+            let (width, height) = info.footprint;
+            let x = (self.x - width as f32 / 2.0).max(0.0).round() as u32;
+            let y = (self.y - height as f32 / 2.0).max(0.0).round() as u32;
+            Rectangle::new(x, y, width, height)
+        })
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut buf = Vec::with_capacity(29);
         try!(buf.write_f32::<LE>(self.x));
         try!(buf.write_f32::<LE>(self.y));
         try!(buf.write_f32::<LE>(2.0));
         try!(buf.write_u32::<LE>(self.id));
-        try!(buf.write_u16::<LE>(self.unit_type as u16));
+        try!(buf.write_u16::<LE>(self.unit_type.id()));
         try!(buf.write_i8(2));
         try!(buf.write_f32::<LE>(self.angle));
         try!(buf.write_u16::<LE>(self.frame));
         try!(buf.write_u32::<LE>(self.garrison_id));
         Ok(buf)
     }
+
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<Unit, Error> {
+        let x = try!(input.read_f32::<LE>());
+        let y = try!(input.read_f32::<LE>());
+        // z
+        try!(input.read_f32::<LE>());
+        let id = try!(input.read_u32::<LE>());
+        let unit_type = UnitTypeId::from_u16(try!(input.read_u16::<LE>()));
+        // state
+        try!(input.read_i8());
+        let angle = try!(input.read_f32::<LE>());
+        let frame = try!(input.read_u16::<LE>());
+        let garrison_id = try!(input.read_u32::<LE>());
+
+        Ok(Unit {
+            id: id,
+            unit_type: unit_type,
+            x: x,
+            y: y,
+            angle: angle,
+            frame: frame,
+            garrison_id: garrison_id,
+        })
+    }
 }
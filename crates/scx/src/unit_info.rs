@@ -0,0 +1,171 @@
+use consts::UnitType;
+
+/// The kind of terrain an object can be placed on.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Placement {
+    Land,
+    Water,
+    /// Shore fish and other objects that can be on land and in water.
+    Amphibious,
+}
+
+/// The player that usually owns an object.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DefaultOwner {
+    Gaia,
+    Player,
+}
+
+/// Placement data for a unit type.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitInfo {
+    /// Size in tiles, as (width, height).
+    pub footprint: (u32, u32),
+    pub placement: Placement,
+    pub is_building: bool,
+    pub default_owner: DefaultOwner,
+}
+
+impl UnitInfo {
+    pub fn can_be_placed_on_land(&self) -> bool {
+        self.placement != Placement::Water
+    }
+
+    pub fn can_be_placed_on_water(&self) -> bool {
+        self.placement != Placement::Land
+    }
+}
+
+// Building sizes as the editor shows them. Other objects, including cliffs, are counted as a
+// single tile; `DatFile` has their exact collision sizes.
+fn footprint(unit_type: UnitType) -> (u32, u32) {
+    match unit_type {
+        UnitType::Wonder => (5, 5),
+        UnitType::TownCenter |
+        UnitType::Castle |
+        UnitType::SiegeWorkshop |
+        UnitType::Market |
+        UnitType::University => (4, 4),
+        UnitType::Barracks |
+        UnitType::ArcheryRange |
+        UnitType::Stable |
+        UnitType::Blacksmith |
+        UnitType::Monastery |
+        UnitType::Dock |
+        UnitType::Farm |
+        UnitType::FishTrap => (3, 3),
+        UnitType::Mill |
+        UnitType::LumberCamp |
+        UnitType::MiningCamp |
+        UnitType::House => (2, 2),
+        // Gates are placed along walls, this is the footprint of an unrotated gate.
+        UnitType::Gate => (1, 4),
+        _ => (1, 1),
+    }
+}
+
+fn placement(unit_type: UnitType) -> Placement {
+    match unit_type {
+        UnitType::FishingShip |
+        UnitType::TradeCog |
+        UnitType::WarGalley |
+        UnitType::Longboat |
+        UnitType::CannonGalleon |
+        UnitType::Galleon |
+        UnitType::DemolitionShip |
+        UnitType::HeavyDemolitionShip |
+        UnitType::FireShip |
+        UnitType::FastFireShip |
+        UnitType::EliteLongboat |
+        UnitType::Galley |
+        UnitType::TransportShip |
+        UnitType::TurtleShip |
+        UnitType::EliteTurtleShip |
+        UnitType::Caravel |
+        UnitType::EliteCaravel |
+        UnitType::Dock |
+        UnitType::FishTrap |
+        UnitType::FishPerch |
+        UnitType::GreatFishMarlin => Placement::Water,
+        UnitType::ShoreFish => Placement::Amphibious,
+        _ => Placement::Land,
+    }
+}
+
+fn is_building(unit_type: UnitType) -> bool {
+    match unit_type {
+        UnitType::Barracks |
+        UnitType::Dock |
+        UnitType::SiegeWorkshop |
+        UnitType::Farm |
+        UnitType::Mill |
+        UnitType::House |
+        UnitType::PalisadeWall |
+        UnitType::WatchTower |
+        UnitType::Castle |
+        UnitType::Market |
+        UnitType::ArcheryRange |
+        UnitType::Stable |
+        UnitType::Blacksmith |
+        UnitType::Monastery |
+        UnitType::TownCenter |
+        UnitType::StoneWall |
+        UnitType::FortifiedWall |
+        UnitType::FishTrap |
+        UnitType::University |
+        UnitType::GuardTower |
+        UnitType::Keep |
+        UnitType::BombardTower |
+        UnitType::Wonder |
+        UnitType::Gate |
+        UnitType::LumberCamp |
+        UnitType::MiningCamp |
+        UnitType::Outpost => true,
+        _ => false,
+    }
+}
+
+fn default_owner(unit_type: UnitType) -> DefaultOwner {
+    match unit_type {
+        UnitType::FishPerch |
+        UnitType::ForageBush |
+        UnitType::GoldMine |
+        UnitType::ShoreFish |
+        UnitType::StoneMine |
+        UnitType::TreeBamboo |
+        UnitType::TreeOak |
+        UnitType::TreePalm |
+        UnitType::TreePine |
+        UnitType::GreatFishMarlin |
+        UnitType::WildBoar |
+        UnitType::Deer |
+        UnitType::Hawk |
+        UnitType::Wolf |
+        UnitType::Sheep |
+        UnitType::Jaguar |
+        UnitType::Javelina |
+        UnitType::Turkey |
+        UnitType::Relic |
+        UnitType::Cliff1 |
+        UnitType::Cliff2 |
+        UnitType::Cliff3 |
+        UnitType::Cliff4 |
+        UnitType::Cliff5 |
+        UnitType::Cliff6 |
+        UnitType::Cliff7 |
+        UnitType::Cliff8 |
+        UnitType::Cliff9 => DefaultOwner::Gaia,
+        _ => DefaultOwner::Player,
+    }
+}
+
+impl UnitType {
+    pub fn info(&self) -> UnitInfo {
+        UnitInfo {
+            footprint: footprint(*self),
+            placement: placement(*self),
+            is_building: is_building(*self),
+            default_owner: default_owner(*self),
+        }
+    }
+}
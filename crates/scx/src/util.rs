@@ -0,0 +1,74 @@
+use std::io::{Read, Write, Error, ErrorKind};
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
+
+// Strings in scenario files are usually NUL-terminated, but not always.
+fn bytes_to_string(mut bytes: Vec<u8>) -> String {
+    match bytes.iter().position(|&b| b == 0) {
+        Some(end) => bytes.truncate(end),
+        None => (),
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+pub fn read_bytes<R: Read>(input: &mut R, len: usize) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0; len];
+    try!(input.read_exact(&mut bytes));
+    Ok(bytes)
+}
+
+pub fn skip<R: Read>(input: &mut R, len: usize) -> Result<(), Error> {
+    try!(read_bytes(input, len));
+    Ok(())
+}
+
+pub fn read_fixed_str<R: Read>(input: &mut R, len: usize) -> Result<String, Error> {
+    Ok(bytes_to_string(try!(read_bytes(input, len))))
+}
+
+pub fn read_str16<R: Read>(input: &mut R) -> Result<String, Error> {
+    let len = try!(input.read_u16::<LE>()) as usize;
+    read_fixed_str(input, len)
+}
+
+pub fn write_str16<W: Write>(output: &mut W, string: &str) -> Result<(), Error> {
+    if string.len() > u16::max_value() as usize {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("string is {} bytes long, the maximum is {}", string.len(), u16::max_value())
+        ));
+    }
+    try!(output.write_u16::<LE>(string.len() as u16));
+    try!(output.write_all(string.as_bytes()));
+    Ok(())
+}
+
+pub fn read_str32<R: Read>(input: &mut R) -> Result<String, Error> {
+    let len = try!(input.read_i32::<LE>());
+    if len < 0 {
+        return Err(Error::new(ErrorKind::InvalidData, "negative string length"));
+    }
+    read_fixed_str(input, len as usize)
+}
+
+// A 32 bit length, NUL-terminated string. Empty strings are stored without the NUL.
+pub fn write_str32<W: Write>(output: &mut W, string: &str) -> Result<(), Error> {
+    if string.is_empty() {
+        try!(output.write_i32::<LE>(0));
+    } else {
+        try!(output.write_i32::<LE>(1 + string.len() as i32));
+        try!(output.write_all(string.as_bytes()));
+        try!(output.write_u8(0));
+    }
+    Ok(())
+}
+
+pub fn expect_u32<R: Read>(input: &mut R, expected: u32, what: &str) -> Result<(), Error> {
+    let value = try!(input.read_u32::<LE>());
+    if value != expected {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("expected {} {:#x}, got {:#x}", what, expected, value)
+        ));
+    }
+    Ok(())
+}
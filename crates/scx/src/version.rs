@@ -0,0 +1,135 @@
+use consts::MapSize;
+
+/// Scenario file formats, named after the game build that reads them.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ScenVersion {
+    /// Age of Kings 1.0, "1.10".
+    AoK,
+    /// Age of Kings 2.0a, "1.18".
+    AoK20,
+    /// The Conquerors 1.0c, "1.21".
+    AoC,
+    /// The Conquerors with UserPatch, "1.22".
+    UserPatch,
+    /// HD Edition and The Forgotten, "1.21" with data version 1.24.
+    HD,
+    /// HD Edition with The African Kingdoms and Rise of the Rajas, "1.21" with data version 1.26.
+    HDRajas,
+}
+
+impl ScenVersion {
+    pub fn from_header(version: &[u8]) -> Option<ScenVersion> {
+        match version {
+            b"1.10" => Some(ScenVersion::AoK),
+            b"1.18" => Some(ScenVersion::AoK20),
+            b"1.21" => Some(ScenVersion::AoC),
+            b"1.22" => Some(ScenVersion::UserPatch),
+            _ => None,
+        }
+    }
+
+    // HD Edition reuses the AoC header version, so the data version at the start of the
+    // body tells them apart.
+    pub fn with_data_version(self, data_version: f32) -> ScenVersion {
+        match (self, (data_version * 100.0).round() as i32) {
+            (ScenVersion::AoC, 124) => ScenVersion::HD,
+            (ScenVersion::AoC, 126) => ScenVersion::HDRajas,
+            _ => self,
+        }
+    }
+
+    // Parse a version as given on the command line, eg. "1.18" or "hd".
+    pub fn from_name(name: &str) -> Option<ScenVersion> {
+        match name {
+            "hd" | "1.24" => Some(ScenVersion::HD),
+            "hd-rajas" | "1.26" => Some(ScenVersion::HDRajas),
+            _ => ScenVersion::from_header(name.as_bytes()),
+        }
+    }
+
+    // The version string at the very start of the file.
+    pub fn header_version(&self) -> &'static [u8; 4] {
+        match *self {
+            ScenVersion::AoK => b"1.10",
+            ScenVersion::AoK20 => b"1.18",
+            ScenVersion::AoC => b"1.21",
+            ScenVersion::UserPatch => b"1.22",
+            ScenVersion::HD | ScenVersion::HDRajas => b"1.21",
+        }
+    }
+
+    // The version number at the start of the compressed body.
+    pub fn data_version(&self) -> f32 {
+        match *self {
+            ScenVersion::AoK => 1.10,
+            ScenVersion::AoK20 => 1.18,
+            ScenVersion::AoC | ScenVersion::UserPatch => 1.22,
+            ScenVersion::HD => 1.24,
+            ScenVersion::HDRajas => 1.26,
+        }
+    }
+
+    pub fn is_hd(&self) -> bool {
+        match *self {
+            ScenVersion::HD | ScenVersion::HDRajas => true,
+            _ => false,
+        }
+    }
+
+    // Largest map that the game can load.
+    pub fn max_map_size(&self) -> u32 {
+        if self.is_hd() {
+            MapSize::Ludicrous as u32
+        } else {
+            MapSize::Max as u32
+        }
+    }
+
+    // HD Edition stores messages with a 32 bit length, so they can be longer than 64KiB.
+    pub fn has_long_messages(&self) -> bool {
+        self.is_hd()
+    }
+
+    // HD Edition stores whether each player's civilization can be changed in the lobby.
+    pub fn has_civilization_locks(&self) -> bool {
+        self.is_hd()
+    }
+
+    // Player names can refer to a string table entry.
+    pub fn has_player_name_ids(&self) -> bool {
+        *self != ScenVersion::AoK
+    }
+
+    // The "Scouts" message was added in The Conquerors.
+    pub fn has_scouts_message(&self) -> bool {
+        self.data_version() >= 1.22
+    }
+
+    // Per-player AI type bytes after the AI source code.
+    pub fn has_ai_types(&self) -> bool {
+        *self != ScenVersion::AoK
+    }
+
+    // Maximum number of disabled technologies per player.
+    pub fn max_disabled_techs(&self) -> usize {
+        match *self {
+            ScenVersion::AoK => 20,
+            _ => 30,
+        }
+    }
+
+    // Disabled units and buildings were added in Age of Kings 2.0.
+    pub fn has_disabled_units(&self) -> bool {
+        *self != ScenVersion::AoK
+    }
+
+    // The population field in the second resources block.
+    pub fn has_population(&self) -> bool {
+        *self != ScenVersion::AoK
+    }
+
+    // The AI type after the editor camera position.
+    pub fn has_view_ai_type(&self) -> bool {
+        self.data_version() >= 1.22
+    }
+}
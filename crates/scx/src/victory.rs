@@ -0,0 +1,98 @@
+use std::io;
+use std::io::prelude::*;
+use byteorder::{LittleEndian as LE, ReadBytesExt, WriteBytesExt};
+
+/// The global victory setting of a scenario.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum VictoryConditions {
+    Standard,
+    Conquest,
+    /// The highest score wins when the time limit, in years, runs out.
+    TimeLimit(i32),
+    /// The first player to reach the score wins.
+    Score(i32),
+    /// Win by fulfilling the enabled goals: any of them, or all of them if `all` is set.
+    Custom {
+        conquest: bool,
+        relics: i32,
+        /// Percentage of the map that must be explored.
+        exploration: i32,
+        all: bool,
+    },
+}
+
+impl VictoryConditions {
+    /// Collect a number of relics.
+    pub fn relics(relics: i32) -> VictoryConditions {
+        VictoryConditions::Custom {
+            conquest: false,
+            relics: relics,
+            exploration: 0,
+            all: false,
+        }
+    }
+
+    /// Explore a percentage of the map.
+    pub fn exploration(percentage: i32) -> VictoryConditions {
+        VictoryConditions::Custom {
+            conquest: false,
+            relics: 0,
+            exploration: percentage,
+            all: false,
+        }
+    }
+
+    // Scenario goals: Conquest; unknown; Relics; unknown; Exploration; unknown;
+    // All; Mode; Score; Time Limit
+    // Modes follow the editor's list: Standard, Conquest, Score, Time Limit, Custom.
+    pub fn from_reader<R: Read>(input: &mut R) -> Result<VictoryConditions, io::Error> {
+        let conquest = try!(input.read_i32::<LE>()) != 0;
+        try!(input.read_i32::<LE>());
+        let relics = try!(input.read_i32::<LE>());
+        try!(input.read_i32::<LE>());
+        let exploration = try!(input.read_i32::<LE>());
+        try!(input.read_i32::<LE>());
+        let all = try!(input.read_i32::<LE>()) != 0;
+        let mode = try!(input.read_i32::<LE>());
+        let score = try!(input.read_i32::<LE>());
+        let time_limit = try!(input.read_i32::<LE>());
+        match mode {
+            0 => Ok(VictoryConditions::Standard),
+            1 => Ok(VictoryConditions::Conquest),
+            2 => Ok(VictoryConditions::Score(score)),
+            3 => Ok(VictoryConditions::TimeLimit(time_limit)),
+            4 => Ok(VictoryConditions::Custom {
+                conquest: conquest,
+                relics: relics,
+                exploration: exploration,
+                all: all,
+            }),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown victory mode {}", mode))),
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, output: &mut W) -> Result<(), io::Error> {
+        let (mode, score, time_limit) = match *self {
+            VictoryConditions::Standard => (0, 0, 0),
+            VictoryConditions::Conquest => (1, 0, 0),
+            VictoryConditions::Score(score) => (2, score, 0),
+            VictoryConditions::TimeLimit(time_limit) => (3, 0, time_limit),
+            VictoryConditions::Custom { .. } => (4, 0, 0),
+        };
+        let (conquest, relics, exploration, all) = match *self {
+            VictoryConditions::Custom { conquest, relics, exploration, all } => (conquest, relics, exploration, all),
+            _ => (false, 0, 0, false),
+        };
+        try!(output.write_i32::<LE>(if conquest { 1 } else { 0 }));
+        try!(output.write_i32::<LE>(0));
+        try!(output.write_i32::<LE>(relics));
+        try!(output.write_i32::<LE>(0));
+        try!(output.write_i32::<LE>(exploration));
+        try!(output.write_i32::<LE>(0));
+        try!(output.write_i32::<LE>(if all { 1 } else { 0 }));
+        try!(output.write_i32::<LE>(mode));
+        try!(output.write_i32::<LE>(score));
+        try!(output.write_i32::<LE>(time_limit));
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+extern crate arms_scx as scx;
+
+use scx::{Civilization, ScenVersion};
+
+#[test]
+fn civilizations_since() {
+    // The first and last civilization of each release.
+    let eras = [
+        (Civilization::Britons, Civilization::Celts, Some(ScenVersion::AoK)),
+        (Civilization::Spanish, Civilization::Koreans, Some(ScenVersion::AoC)),
+        (Civilization::Italians, Civilization::Slavs, Some(ScenVersion::HD)),
+        (Civilization::Portuguese, Civilization::Vietnamese, Some(ScenVersion::HDRajas)),
+        // Definitive Edition
+        (Civilization::Bulgarians, Civilization::Georgians, None),
+    ];
+    for &(first, last, version) in eras.iter() {
+        assert_eq!(first.info().since, version, "{}", first.name());
+        assert_eq!(last.info().since, version, "{}", last.name());
+    }
+}
+
+#[test]
+fn civilizations_available_in() {
+    assert!(Civilization::Koreans.is_available_in(ScenVersion::AoC));
+    assert!(!Civilization::Koreans.is_available_in(ScenVersion::AoK));
+    assert!(!Civilization::Italians.is_available_in(ScenVersion::AoC));
+    assert!(!Civilization::Georgians.is_available_in(ScenVersion::HDRajas));
+}
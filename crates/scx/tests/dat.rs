@@ -0,0 +1,75 @@
+extern crate arms_scx as scx;
+
+use scx::DatFile;
+
+// Regenerate the fixture with `python3 tests/fixtures/make_dat.py`.
+fn fixture() -> DatFile {
+    DatFile::open("tests/fixtures/small.dat").unwrap()
+}
+
+#[test]
+fn reads_terrains() {
+    let dat = fixture();
+    assert_eq!(dat.terrains.len(), 42);
+    assert_eq!(dat.terrains[0].name, "Grass");
+    assert!(dat.terrains[0].enabled);
+    assert_eq!(dat.terrains[1].id, 1);
+    assert_eq!(dat.terrains[1].name, "Water");
+    assert!(!dat.terrains[2].enabled);
+}
+
+#[test]
+fn reads_civilizations() {
+    let dat = fixture();
+    assert_eq!(dat.civilizations.len(), 2);
+    let britons = &dat.civilizations[1];
+    assert_eq!(britons.id, 1);
+    assert_eq!(britons.name, "Britons");
+    assert_eq!(britons.tech_tree, 254);
+    assert_eq!(britons.team_bonus, -1);
+    // Empty unit slots are skipped.
+    assert_eq!(britons.units.len(), 4);
+}
+
+#[test]
+fn reads_units() {
+    let dat = fixture();
+    let ids: Vec<u16> = dat.civilizations[0].units.iter().map(|unit| unit.id).collect();
+    assert_eq!(ids, vec![411, 4, 109, 600]);
+
+    let tree = dat.unit(411).unwrap();
+    assert_eq!(tree.kind, 90);
+    assert_eq!(tree.name, "TREE1");
+    assert!(tree.hide_in_editor);
+    assert_eq!(tree.footprint(), (1, 1));
+
+    let archer = dat.unit(4).unwrap();
+    assert_eq!(archer.name, "ARCHR");
+    assert_eq!(archer.hit_points, 30);
+    assert!(!archer.is_building());
+
+    let town_center = dat.unit(109).unwrap();
+    assert_eq!(town_center.class, 3);
+    assert!(town_center.is_building());
+    assert_eq!(town_center.footprint(), (4, 4));
+
+    assert_eq!(dat.unit(600).unwrap().name, "FLAGA");
+    assert!(dat.unit(1).is_none());
+}
+
+#[test]
+fn reads_techs() {
+    let dat = fixture();
+    assert_eq!(dat.techs.len(), 2);
+    let feudal = &dat.techs[0];
+    assert_eq!(feudal.name, "Feudal Age");
+    assert!(feudal.required_techs.is_empty());
+    assert_eq!(feudal.research_location, 109);
+    assert_eq!(feudal.research_time, 130);
+
+    let yeomen = &dat.techs[1];
+    assert_eq!(yeomen.id, 1);
+    assert_eq!(yeomen.required_techs, vec![0]);
+    assert_eq!(yeomen.required_tech_count, 1);
+    assert_eq!(yeomen.civilization, 1);
+}
@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+# Generate small.dat, a tiny VER 5.7 .dat file for the tests in ../dat.rs. Only the fields
+# that the reader looks at have interesting values, everything else is zeroed.
+import os
+import struct
+import zlib
+
+TERRAIN_SLOTS = 42
+TERRAIN_BORDERS = 16
+TILE_TYPES = 19
+
+
+def u8(n): return struct.pack('<B', n)
+def u16(n): return struct.pack('<H', n)
+def i16(n): return struct.pack('<h', n)
+def u32(n): return struct.pack('<I', n)
+def i32(n): return struct.pack('<i', n)
+def f32(n): return struct.pack('<f', n)
+def zero(n): return b'\0' * n
+def fixed(s, n): return s.encode('ascii').ljust(n, b'\0')
+
+
+def terrain(name, enabled):
+    return (u8(1 if enabled else 0) + u8(0) + fixed(name, 13) + zero(63) +
+            zero(TILE_TYPES * 6) + zero(2 + 4 + TERRAIN_SLOTS * 2 + 30 * 2 + 30 * 2 + 30 + 2 + 2))
+
+
+def terrain_block():
+    out = zero(6 * 4 + TILE_TYPES * 6 + 2)
+    for i in range(TERRAIN_SLOTS):
+        out += terrain('Grass' if i == 0 else 'Water' if i == 1 else '', i < 2)
+    out += zero(TERRAIN_BORDERS * (66 + TILE_TYPES * 12 * 6 + 6))
+    return out + zero(67) + zero(21 + 157 * 4)
+
+
+def random_maps():
+    out = u32(1) + zero(4) + zero(18 * 4)
+    out += zero(10 * 4)
+    # One land, two terrains, one unit, no elevations
+    for count, size in [(1, 44), (2, 24), (1, 44), (0, 24)]:
+        out += u32(count) + zero(4) + zero(count * size)
+    return out
+
+
+def unit(kind, id, name, class_, hp, size, hidden=False):
+    out = u8(kind) + u16(len(name)) + u16(id) + zero(4) + i16(class_) + zero(9) + i16(hp)
+    out += zero(5) + f32(size[0]) + f32(size[1]) + zero(14) + u8(1 if hidden else 0)
+    out += zero(41 + 23 + 14 + 3 * 7)
+    # One damage graphic
+    out += u8(1) + zero(5)
+    out += zero(6) + name.encode('ascii') + zero(4)
+    if 20 <= kind <= 80:
+        out += zero(4)
+    if 30 <= kind <= 80:
+        out += zero(37)
+    if 40 <= kind <= 80:
+        out += zero(20)
+    if 50 <= kind <= 80:
+        # One attack, two armours
+        out += zero(2) + u16(1) + zero(4) + u16(2) + zero(8) + zero(56)
+    if kind == 60:
+        out += zero(9)
+    if 70 <= kind <= 80:
+        out += zero(23 + 42)
+    if kind == 80:
+        out += zero(16 + 66)
+    return out
+
+
+def civilization(name, tech_tree, units):
+    out = u8(1) + fixed(name, 20) + u16(2) + i16(tech_tree) + i16(-1) + zero(2 * 4) + u8(0)
+    out += u16(len(units) + 1)
+    # An empty slot between the units
+    out += i32(1) * 2 + i32(0) + i32(1) * (len(units) - 2)
+    for u in units:
+        out += u
+    return out
+
+
+def tech(name, required, civ, location, time):
+    required = required + [-1] * (6 - len(required))
+    out = b''.join(i16(r) for r in required) + zero(15)
+    out += i16(1 if required[0] >= 0 else 0) + i16(civ) + i16(0) + i16(location) + zero(4)
+    out += i16(time) + zero(19) + u16(len(name)) + name.encode('ascii')
+    return out
+
+
+def units():
+    return [
+        # Trees only have the common fields.
+        unit(90, 411, 'TREE1', 15, 20, (0.5, 0.5), hidden=True),
+        unit(70, 4, 'ARCHR', 0, 30, (0.2, 0.2)),
+        unit(80, 109, 'RTWC', 3, 2400, (2.0, 2.0)),
+        unit(20, 600, 'FLAGA', 11, 1, (0.1, 0.1)),
+    ]
+
+
+def main():
+    body = fixed('VER 5.7', 8)
+    # Terrain restrictions, player colours, sounds, graphics
+    body += u16(0) + u16(0) + u16(0) + u16(0) + u16(0)
+    body += terrain_block()
+    body += random_maps()
+    # Technology effects, unit tasks
+    body += u32(0) + u32(0)
+    body += u16(2)
+    body += civilization('Gaia', -1, units())
+    body += civilization('Britons', 254, units())
+    body += u16(2)
+    body += tech('Feudal Age', [], -1, 109, 130)
+    body += tech('Yeomen', [0], 1, 82, 60)
+
+    compressor = zlib.compressobj(9, zlib.DEFLATED, -15)
+    data = compressor.compress(body) + compressor.flush()
+    with open(os.path.join(os.path.dirname(__file__), 'small.dat'), 'wb') as f:
+        f.write(data)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,63 @@
+extern crate arms_scx as scx;
+
+use scx::{Bitmap, Map, Player, Scenario, ScenImage, ScenVersion, Unit, UnitTypeId};
+
+#[test]
+fn reads_unknown_unit_types() {
+    let mut player = Player::empty();
+    player.active = 1;
+    let player = player.with_units(vec![Unit::new(UnitTypeId::Raw(4000), 10.0, 10.0)]);
+    let bytes = Scenario::new(ScenVersion::AoC)
+        .with_players(vec![player])
+        .with_gaia_units(vec![Unit::new(UnitTypeId::Raw(4001), 5.0, 5.0)])
+        .to_bytes().unwrap();
+
+    let read = Scenario::from_bytes(&bytes).unwrap();
+    assert!(read.players[0].units[0].unit_type == UnitTypeId::Raw(4000));
+    assert!(read.gaia_units[0].unit_type == UnitTypeId::Raw(4001));
+}
+
+#[test]
+fn reads_embedded_bitmaps() {
+    let map = Map::filled(40, 0);
+    let image = ScenImage {
+        filename: String::from("intro.bmp"),
+        bitmap: Some(Bitmap::minimap(&map, 30, 15)),
+        include: 1,
+    };
+    let bytes = Scenario::new(ScenVersion::AoC).with_map(map).with_image(image).to_bytes().unwrap();
+
+    let read = Scenario::from_bytes(&bytes).unwrap();
+    assert_eq!(read.image.filename, "intro.bmp");
+    let bitmap = read.image.bitmap.unwrap();
+    assert_eq!((bitmap.width, bitmap.height, bitmap.bit_count), (30, 15, 8));
+    assert_eq!(bitmap.pixels.len(), 32 * 15);
+}
+
+fn bitmap_header(header_size: u32, compression: u32, image_size: u32) -> Vec<u8> {
+    let mut bytes = vec![];
+    for &(value, size) in &[(header_size, 4), (2, 4), (2, 4), (1, 2), (8, 2), (compression, 4),
+                            (image_size, 4), (0, 4), (0, 4), (1, 4), (0, 4)] {
+        bytes.extend(value.to_le_bytes()[..size].iter().cloned());
+    }
+    bytes.extend(vec![0; header_size as usize - 40]);
+    // Palette
+    bytes.extend(vec![0, 0, 0, 0]);
+    bytes
+}
+
+#[test]
+fn skips_bitmaps_it_can_not_represent() {
+    // Compressed, with 6 bytes of pixel data
+    let mut bytes = bitmap_header(40, 1, 6);
+    bytes.extend(vec![0; 6]);
+    // Uncompressed, with a BITMAPV4HEADER
+    bytes.extend(bitmap_header(108, 0, 0));
+    bytes.extend(vec![0; 8]);
+    bytes.push(0xFF);
+
+    let mut input = &bytes[..];
+    assert!(Bitmap::read_or_skip(&mut input).unwrap().is_none());
+    assert!(Bitmap::read_or_skip(&mut input).unwrap().is_none());
+    assert_eq!(input, &[0xFF]);
+}
@@ -0,0 +1,70 @@
+extern crate arms_scx as scx;
+
+use scx::{Civilization, Player, Scenario, ScenMessages, ScenVersion, Unit, UnitType};
+
+const VERSIONS: &'static [ScenVersion] = &[
+    ScenVersion::AoK,
+    ScenVersion::AoK20,
+    ScenVersion::AoC,
+    ScenVersion::UserPatch,
+    ScenVersion::HD,
+    ScenVersion::HDRajas,
+];
+
+fn scenario(version: ScenVersion) -> Scenario {
+    let mut player = Player::empty();
+    player.name = String::from("Player 1");
+    player.active = 1;
+    player.human = 2;
+    player.civilization = Civilization::Britons;
+    let player = player.with_units(vec![
+        Unit::new(UnitType::TownCenter, 20.0, 20.0),
+        Unit::new(UnitType::Villager, 24.0, 22.0),
+    ]);
+    let mut players = vec![player];
+    players.extend((1..8).map(|_| Player::empty()));
+
+    let mut messages = ScenMessages::new();
+    messages.objectives = String::from("Build a wonder.");
+    messages.hints = String::from("Gather stone.");
+
+    Scenario::new(version)
+        .with_filename("test.scx")
+        .with_players(players)
+        .with_messages(messages)
+        .with_gaia_units(vec![Unit::new(UnitType::Relic, 60.0, 60.0)])
+}
+
+#[test]
+fn writes_and_reads_every_version() {
+    for &version in VERSIONS {
+        let bytes = scenario(version).to_bytes().unwrap();
+        let read = Scenario::from_bytes(&bytes).unwrap();
+        assert_eq!(read.messages.objectives, "Build a wonder.");
+        assert_eq!(read.players[0].units.len(), 2);
+        let difference = scx::round_trip(&bytes).unwrap();
+        assert!(difference.is_none(), "{:?} does not round trip: {:?}", version, difference);
+    }
+}
+
+#[test]
+fn rejects_invalid_header_lengths() {
+    let mut bytes = scenario(ScenVersion::AoC).to_bytes().unwrap();
+    bytes[4..8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+    assert!(scx::compare(&bytes, &bytes).is_err());
+    bytes[4..8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0x7F]);
+    assert!(scx::compare(&bytes, &bytes).is_err());
+}
+
+#[test]
+fn rejects_strings_that_do_not_fit() {
+    let mut players = vec![Player::empty()];
+    players[0].name = "x".repeat(256);
+    assert!(Scenario::new(ScenVersion::AoC).with_players(players).to_bytes().is_err());
+
+    let mut messages = ScenMessages::new();
+    messages.hints = "x".repeat(70000);
+    assert!(Scenario::new(ScenVersion::AoK).with_messages(messages).to_bytes().is_err());
+
+    assert!(Scenario::new(ScenVersion::AoC).with_filename(&"x".repeat(70000)).to_bytes().is_err());
+}
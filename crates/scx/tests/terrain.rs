@@ -0,0 +1,42 @@
+extern crate arms_scx as scx;
+
+use std::fs::File;
+use std::io::prelude::*;
+use scx::{ScenVersion, Terrain};
+
+#[test]
+fn terrain_ids() {
+    for (id, info) in Terrain::all().iter().enumerate() {
+        assert_eq!(info.terrain as usize, id, "{}", info.name);
+        assert!(Terrain::from_u8(id as u8).unwrap() == info.terrain);
+    }
+    assert!(Terrain::from_u8(Terrain::all().len() as u8).is_err());
+    assert!(Terrain::Grass1.is_available_in(ScenVersion::AoK));
+    assert!(!Terrain::Snow.is_available_in(ScenVersion::AoK20));
+    assert!(Terrain::Snow.is_available_in(ScenVersion::AoC));
+}
+
+#[test]
+#[allow(deprecated)]
+fn old_terrain_names() {
+    assert!(Terrain::Grass == Terrain::Grass1);
+    assert!(Terrain::Water == Terrain::WaterShallow);
+}
+
+// The Lua rock has its own copy of the terrain table, so it also works without `arms`.
+#[test]
+fn lua_terrain_table_matches() {
+    let mut constants = String::new();
+    File::open("../../rocks/arms/src/constants.moon").unwrap()
+        .read_to_string(&mut constants).unwrap();
+    let table: Vec<&str> = constants.lines()
+        .skip_while(|line| *line != "terrain =")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    assert_eq!(table.len(), Terrain::all().len());
+    for info in Terrain::all() {
+        let entry = format!("  {}: {}", info.lua_name, info.terrain as u8);
+        assert!(table.contains(&&entry[..]), "missing {} in constants.moon", entry);
+    }
+}
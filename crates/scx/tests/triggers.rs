@@ -0,0 +1,140 @@
+extern crate arms_scx as scx;
+extern crate byteorder;
+
+use byteorder::{LittleEndian as LE, WriteBytesExt};
+use scx::{Coordinate, Rectangle, Scenario, ScenVersion, Trigger, TriggerCondition, TriggerEffect};
+
+fn condition(extra_fields: Vec<i32>) -> TriggerCondition {
+    TriggerCondition {
+        condition: 10,
+        check: 16,
+        amount: -1,
+        resource: -1,
+        unit_object: -1,
+        unit_location: -1,
+        unit_type: -1,
+        player: -1,
+        technology: -1,
+        timer: 30,
+        area: Rectangle::new(!0, !0, 0, 0),
+        unit_group: -1,
+        object_type: -1,
+        ai_signal: -1,
+        extra_fields: extra_fields,
+        display_order: 0,
+    }
+}
+
+fn effect(extra_fields: Vec<i32>) -> TriggerEffect {
+    TriggerEffect {
+        effect: 3,
+        check: 23,
+        ai_goal: -1,
+        amount: -1,
+        resource: -1,
+        diplomacy: -1,
+        unit_location: -1,
+        unit_type: -1,
+        player_source: 1,
+        player_target: -1,
+        technology: -1,
+        text_id: -1,
+        display_time: -1,
+        trigger_index: -1,
+        location: Coordinate::new(!0, !0),
+        area: Rectangle::new(2, 3, 4, 5),
+        unit_group: -1,
+        object_type: -1,
+        instruction_panel: -1,
+        extra_fields: extra_fields,
+        text: String::from("Hello"),
+        sound_filename: String::new(),
+        unit_ids: Some(vec![7]),
+        display_order: 0,
+    }
+}
+
+fn trigger(conditions: Vec<TriggerCondition>, effects: Vec<TriggerEffect>) -> Trigger {
+    Trigger {
+        enabled: true,
+        is_looping: false,
+        is_objective: true,
+        objective_order: 2,
+        name: String::from("Greeting"),
+        description: String::from("Say hello"),
+        description_id: -1,
+        short_description: String::new(),
+        short_description_id: -1,
+        display_on_screen: false,
+        display_order: 0,
+        conditions: conditions,
+        effects: effects,
+    }
+}
+
+fn write_and_read(version: ScenVersion, triggers: Vec<Trigger>) -> Scenario {
+    let bytes = Scenario::new(version).with_triggers(triggers).to_bytes().unwrap();
+    let difference = scx::round_trip(&bytes).unwrap();
+    assert!(difference.is_none(), "{:?} triggers do not round trip: {:?}", version, difference);
+    Scenario::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn triggers_keep_their_extra_fields() {
+    let extra_condition_fields = vec![1, 2, 3, 4, 5];
+    let extra_effect_fields: Vec<i32> = (0..23).collect();
+    let read = write_and_read(ScenVersion::HD, vec![
+        trigger(vec![condition(extra_condition_fields.clone())], vec![effect(extra_effect_fields.clone())]),
+    ]);
+    let trigger = &read.triggers[0];
+    assert_eq!(trigger.conditions[0].check, 21);
+    assert_eq!(trigger.conditions[0].timer, 30);
+    assert_eq!(trigger.conditions[0].extra_fields, extra_condition_fields);
+    assert_eq!(trigger.effects[0].check, 46);
+    assert_eq!(trigger.effects[0].extra_fields, extra_effect_fields);
+    assert_eq!(trigger.effects[0].text, "Hello");
+    assert_eq!(trigger.effects[0].unit_ids, Some(vec![7]));
+}
+
+#[test]
+fn short_descriptions_cannot_be_written() {
+    let mut trigger = trigger(vec![], vec![]);
+    trigger.short_description = String::from("Hello");
+    assert!(Scenario::new(ScenVersion::HD).with_triggers(vec![trigger]).to_bytes().is_err());
+}
+
+#[test]
+fn description_ids_cannot_be_written() {
+    let mut trigger = trigger(vec![], vec![]);
+    trigger.description_id = 4242;
+    assert!(Scenario::new(ScenVersion::HD).with_triggers(vec![trigger]).to_bytes().is_err());
+}
+
+#[test]
+fn aoc_triggers_use_the_aoc_layout() {
+    let read = write_and_read(ScenVersion::AoC, vec![trigger(vec![condition(vec![])], vec![effect(vec![])])]);
+    let trigger = &read.triggers[0];
+    assert_eq!(trigger.conditions[0].check, 16);
+    assert_eq!(trigger.effects[0].check, 23);
+    assert!(trigger.effects[0].extra_fields.is_empty());
+}
+
+#[test]
+fn effects_keep_the_missing_selection_marker() {
+    // A Send Chat effect as the editor writes it: no selected units, so the unit count is -1.
+    let mut bytes = vec![];
+    let mut fields = vec![3, 23, -1, -1, -1, -1, -1, -1, -1, 1, -1, -1, -1, -1, -1, -1, -1, -1];
+    fields.extend(&[-1, -1, -1, -1, -1, -1, 0]);
+    for &field in &fields {
+        bytes.write_i32::<LE>(field).unwrap();
+    }
+    // Text and sound file name
+    bytes.write_i32::<LE>(0).unwrap();
+    bytes.write_i32::<LE>(0).unwrap();
+
+    let effect = TriggerEffect::from_reader(&mut &bytes[..]).unwrap();
+    assert_eq!(effect.unit_ids, None);
+    let mut written = vec![];
+    effect.write_to(&mut written).unwrap();
+    assert_eq!(written, bytes);
+}
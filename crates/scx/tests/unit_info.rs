@@ -0,0 +1,63 @@
+extern crate arms_scx as scx;
+
+use scx::{DefaultOwner, Placement, Rectangle, UnitType};
+
+#[test]
+fn building_footprints() {
+    assert_eq!(UnitType::Wonder.info().footprint, (5, 5));
+    assert_eq!(UnitType::TownCenter.info().footprint, (4, 4));
+    assert_eq!(UnitType::Barracks.info().footprint, (3, 3));
+    assert_eq!(UnitType::House.info().footprint, (2, 2));
+    assert_eq!(UnitType::Gate.info().footprint, (1, 4));
+    assert_eq!(UnitType::Villager.info().footprint, (1, 1));
+}
+
+#[test]
+fn placements() {
+    assert_eq!(UnitType::Galley.info().placement, Placement::Water);
+    assert_eq!(UnitType::Dock.info().placement, Placement::Water);
+    assert_eq!(UnitType::ShoreFish.info().placement, Placement::Amphibious);
+    assert_eq!(UnitType::Villager.info().placement, Placement::Land);
+    assert!(UnitType::ShoreFish.info().can_be_placed_on_land());
+    assert!(UnitType::ShoreFish.info().can_be_placed_on_water());
+    assert!(!UnitType::Galley.info().can_be_placed_on_land());
+    assert!(!UnitType::Villager.info().can_be_placed_on_water());
+}
+
+#[test]
+fn buildings_and_owners() {
+    assert!(UnitType::Castle.info().is_building);
+    assert!(UnitType::Gate.info().is_building);
+    assert!(!UnitType::Villager.info().is_building);
+    assert!(!UnitType::GoldMine.info().is_building);
+    assert_eq!(UnitType::GoldMine.info().default_owner, DefaultOwner::Gaia);
+    assert_eq!(UnitType::Relic.info().default_owner, DefaultOwner::Gaia);
+    assert_eq!(UnitType::Cliff1.info().default_owner, DefaultOwner::Gaia);
+    assert_eq!(UnitType::Villager.info().default_owner, DefaultOwner::Player);
+}
+
+#[test]
+fn rectangles_intersect() {
+    let rect = Rectangle::new(10, 10, 4, 4);
+    // Overlapping, containing and contained
+    assert!(rect.intersects(&Rectangle::new(12, 12, 4, 4)));
+    assert!(rect.intersects(&Rectangle::new(0, 0, 20, 20)));
+    assert!(rect.intersects(&Rectangle::new(11, 11, 1, 1)));
+    // Sharing a corner tile
+    assert!(rect.intersects(&Rectangle::new(13, 13, 2, 2)));
+    assert!(rect.intersects(&Rectangle::new(8, 8, 3, 3)));
+}
+
+#[test]
+fn touching_rectangles_do_not_intersect() {
+    let rect = Rectangle::new(10, 10, 4, 4);
+    // Right next to each edge
+    assert!(!rect.intersects(&Rectangle::new(14, 10, 2, 4)));
+    assert!(!rect.intersects(&Rectangle::new(8, 10, 2, 4)));
+    assert!(!rect.intersects(&Rectangle::new(10, 14, 4, 2)));
+    assert!(!rect.intersects(&Rectangle::new(10, 8, 4, 2)));
+    // Diagonally next to a corner
+    assert!(!rect.intersects(&Rectangle::new(14, 14, 1, 1)));
+    // Empty rectangles cover no tiles
+    assert!(!rect.intersects(&Rectangle::new(11, 11, 0, 0)));
+}
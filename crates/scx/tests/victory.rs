@@ -0,0 +1,33 @@
+extern crate arms_scx as scx;
+extern crate byteorder;
+
+use byteorder::{LittleEndian as LE, WriteBytesExt};
+use scx::VictoryConditions;
+
+// The goals block as the editor writes it: Conquest; unknown; Relics; unknown; Exploration;
+// unknown; All; Mode; Score; Time Limit
+fn goals(mode: i32, score: i32, time_limit: i32) -> Vec<u8> {
+    let mut bytes = vec![];
+    for &field in [0, 0, 0, 0, 0, 0, 0, mode, score, time_limit].iter() {
+        bytes.write_i32::<LE>(field).unwrap();
+    }
+    bytes
+}
+
+#[test]
+fn reads_score_and_time_limit() {
+    let score = VictoryConditions::from_reader(&mut &goals(2, 900, 0)[..]).unwrap();
+    assert_eq!(score, VictoryConditions::Score(900));
+    let time_limit = VictoryConditions::from_reader(&mut &goals(3, 0, 350)[..]).unwrap();
+    assert_eq!(time_limit, VictoryConditions::TimeLimit(350));
+}
+
+#[test]
+fn writes_score_and_time_limit() {
+    let mut score = vec![];
+    VictoryConditions::Score(900).write_to(&mut score).unwrap();
+    assert_eq!(score, goals(2, 900, 0));
+    let mut time_limit = vec![];
+    VictoryConditions::TimeLimit(350).write_to(&mut time_limit).unwrap();
+    assert_eq!(time_limit, goals(3, 0, 350));
+}